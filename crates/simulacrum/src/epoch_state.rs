@@ -4,6 +4,7 @@
 use std::{collections::HashSet, sync::Arc};
 
 use anyhow::Result;
+use sui_config::transaction_check_config::TransactionCheckConfig;
 use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_execution::Executor;
 use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
@@ -112,6 +113,12 @@ impl EpochState {
             &receiving_object_refs,
         )?;
 
+        sui_transaction_checks::deny::check_object_types_for_signing(
+            &input_objects,
+            &receiving_objects,
+            deny_config,
+        )?;
+
         // Run the transaction input checks that would run when submitting the txn to a validator
         // for signing
         let (gas_status, checked_input_objects) = sui_transaction_checks::check_transaction_input(
@@ -121,6 +128,7 @@ impl EpochState {
             input_objects,
             receiving_objects,
             &self.bytecode_verifier_metrics,
+            &TransactionCheckConfig::default(),
         )?;
 
         let transaction_data = transaction.data().transaction_data();
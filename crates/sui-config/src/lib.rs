@@ -15,6 +15,7 @@ pub mod local_ip_utils;
 pub mod node;
 pub mod node_config_metrics;
 pub mod p2p;
+pub mod transaction_check_config;
 pub mod transaction_deny_config;
 
 pub use node::{ConsensusConfig, NodeConfig};
@@ -4,6 +4,7 @@
 use crate::certificate_deny_config::CertificateDenyConfig;
 use crate::genesis;
 use crate::p2p::P2pConfig;
+use crate::transaction_check_config::TransactionCheckConfig;
 use crate::transaction_deny_config::TransactionDenyConfig;
 use crate::Config;
 use anyhow::Result;
@@ -133,6 +134,9 @@ pub struct NodeConfig {
     #[serde(default)]
     pub certificate_deny_config: CertificateDenyConfig,
 
+    #[serde(default)]
+    pub transaction_check_config: TransactionCheckConfig,
+
     #[serde(default)]
     pub state_debug_dump_config: StateDebugDumpConfig,
 
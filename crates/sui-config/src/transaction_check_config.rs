@@ -0,0 +1,39 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+/// Default fraction of the verifier meter budget above which a successfully verified package is
+/// logged as close to timing out.
+pub const DEFAULT_VERIFIER_METER_WARN_FRACTION: f64 = 0.9;
+
+/// Node-local settings for the transaction input checks run when signing. Unlike the protocol
+/// config these never change which transactions are accepted, only how they are checked and what
+/// is reported about them.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TransactionCheckConfig {
+    /// Number of input objects above which a transaction is logged and counted as unusually
+    /// large, while still being accepted as long as it is within `max_input_objects`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_object_warn_threshold: Option<usize>,
+
+    /// Fraction of the verifier meter budget, in `[0, 1]`, above which a successful package
+    /// verification is logged as close to timing out. Defaults to
+    /// `DEFAULT_VERIFIER_METER_WARN_FRACTION`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifier_meter_warn_fraction: Option<f64>,
+
+    /// Verify the packages published or upgraded by one transaction concurrently. The packages
+    /// still share the verifier budget they would have had one after the other, so this only
+    /// changes how fast a transaction is checked, not whether it is accepted.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub parallel_package_verification: bool,
+}
+
+impl TransactionCheckConfig {
+    pub fn verifier_meter_warn_fraction(&self) -> f64 {
+        self.verifier_meter_warn_fraction
+            .unwrap_or(DEFAULT_VERIFIER_METER_WARN_FRACTION)
+    }
+}
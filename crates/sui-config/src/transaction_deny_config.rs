@@ -3,6 +3,7 @@
 
 use std::collections::HashSet;
 
+use move_core_types::language_storage::StructTag;
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -71,6 +72,28 @@ pub struct TransactionDenyConfig {
     /// A list of disabled OAuth providers for zkLogin
     #[serde(default)]
     zklogin_disabled_providers: HashSet<String>,
+
+    /// A set of transaction kinds that are not allowed to be signed.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    denied_transaction_kinds: HashSet<TransactionKindTag>,
+
+    /// A list of Move functions that are not allowed to be called directly from programmable
+    /// transactions. Like the package deny list, the package is matched against the package ID
+    /// used in the call, so other versions of the package can still be called.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    function_deny_list: Vec<DeniedMoveFunction>,
+
+    #[serde(skip)]
+    function_deny_set: OnceCell<HashSet<DeniedMoveFunction>>,
+
+    /// A list of Move types whose objects are not allowed to be used as input or receiving
+    /// objects in transactions. Type parameters are matched exactly, so e.g.
+    /// `0x2::coin::Coin<0x2::sui::SUI>` can be denied without denying coins of other types.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    type_deny_list: Vec<StructTag>,
+
+    #[serde(skip)]
+    type_deny_set: OnceCell<HashSet<StructTag>>,
     // TODO: We could consider add a deny list for types that we want to disable public transfer.
     // TODO: We could also consider disable more types of commands, such as transfer, split and etc.
 }
@@ -91,6 +114,32 @@ impl TransactionDenyConfig {
             .get_or_init(|| self.address_deny_list.iter().cloned().collect())
     }
 
+    pub fn get_function_deny_set(&self) -> &HashSet<DeniedMoveFunction> {
+        self.function_deny_set
+            .get_or_init(|| self.function_deny_list.iter().cloned().collect())
+    }
+
+    pub fn get_type_deny_set(&self) -> &HashSet<StructTag> {
+        self.type_deny_set
+            .get_or_init(|| self.type_deny_list.iter().cloned().collect())
+    }
+
+    /// Whether calling `package::module::function` is denied, either by name or because its whole
+    /// module is.
+    pub fn is_function_denied(&self, package: ObjectID, module: &str, function: &str) -> bool {
+        let deny_set = self.get_function_deny_set();
+        !deny_set.is_empty()
+            && [function, DeniedMoveFunction::ANY_FUNCTION]
+                .into_iter()
+                .any(|function| {
+                    deny_set.contains(&DeniedMoveFunction {
+                        package,
+                        module: module.to_string(),
+                        function: function.to_string(),
+                    })
+                })
+    }
+
     pub fn package_publish_disabled(&self) -> bool {
         self.package_publish_disabled
     }
@@ -118,6 +167,36 @@ impl TransactionDenyConfig {
     pub fn zklogin_disabled_providers(&self) -> &HashSet<String> {
         &self.zklogin_disabled_providers
     }
+
+    pub fn denied_transaction_kinds(&self) -> &HashSet<TransactionKindTag> {
+        &self.denied_transaction_kinds
+    }
+}
+
+/// Classes of transactions that can be denied through `denied_transaction_kinds`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransactionKindTag {
+    /// Any programmable transaction.
+    ProgrammableTransaction,
+    /// Programmable transactions containing a `Publish` command.
+    Publish,
+    /// Programmable transactions containing an `Upgrade` command.
+    Upgrade,
+}
+
+/// A Move function that can be denied through `function_deny_list`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DeniedMoveFunction {
+    pub package: ObjectID,
+    pub module: String,
+    /// The function name, or `*` to deny every function in the module.
+    pub function: String,
+}
+
+impl DeniedMoveFunction {
+    pub const ANY_FUNCTION: &'static str = "*";
 }
 
 #[derive(Default)]
@@ -183,4 +262,23 @@ impl TransactionDenyConfigBuilder {
         self.config.zklogin_disabled_providers.insert(provider);
         self
     }
+
+    pub fn add_denied_transaction_kind(mut self, kind: TransactionKindTag) -> Self {
+        self.config.denied_transaction_kinds.insert(kind);
+        self
+    }
+
+    pub fn add_denied_type(mut self, type_: StructTag) -> Self {
+        self.config.type_deny_list.push(type_);
+        self
+    }
+
+    pub fn add_denied_function(mut self, package: ObjectID, module: &str, function: &str) -> Self {
+        self.config.function_deny_list.push(DeniedMoveFunction {
+            package,
+            module: module.to_string(),
+            function: function.to_string(),
+        });
+        self
+    }
 }
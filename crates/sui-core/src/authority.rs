@@ -62,6 +62,7 @@ use sui_config::genesis::Genesis;
 use sui_config::node::{
     AuthorityStorePruningConfig, DBCheckpointConfig, ExpensiveSafetyCheckConfig,
 };
+use sui_config::transaction_check_config::TransactionCheckConfig;
 use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_framework::{BuiltInFramework, SystemPackage};
 use sui_json_rpc_types::{
@@ -187,6 +188,7 @@ pub struct AuthorityMetrics {
     pub shared_obj_tx: IntCounter,
     sponsored_tx: IntCounter,
     tx_already_processed: IntCounter,
+    tx_input_objects_above_warn_threshold: IntCounter,
     num_input_objs: Histogram,
     num_shared_objects: Histogram,
     batch_size: Histogram,
@@ -326,6 +328,12 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            tx_input_objects_above_warn_threshold: register_int_counter_with_registry!(
+                "num_tx_input_objects_above_warn_threshold",
+                "Number of signed transactions with more input objects than the configured warning threshold",
+                registry,
+            )
+            .unwrap(),
             num_input_objs: register_histogram_with_registry!(
                 "num_input_objects",
                 "Distribution of number of input TX objects per TX",
@@ -639,10 +647,13 @@ pub struct AuthorityState {
     /// Config controlling what kind of expensive safety checks to perform.
     expensive_safety_check_config: ExpensiveSafetyCheckConfig,
 
-    transaction_deny_config: TransactionDenyConfig,
+    /// Swappable at runtime through `transaction_deny_config_handle`.
+    transaction_deny_config: Arc<ArcSwap<TransactionDenyConfig>>,
 
     certificate_deny_config: CertificateDenyConfig,
 
+    transaction_check_config: TransactionCheckConfig,
+
     /// Config for state dumping on forks
     debug_dump_config: StateDebugDumpConfig,
 
@@ -695,6 +706,12 @@ impl AuthorityState {
         Ok(commitments)
     }
 
+    /// Handle to the deny config used when signing transactions and in dry runs. Storing a new
+    /// config through it applies to every transaction checked afterwards, without a restart.
+    pub fn transaction_deny_config_handle(&self) -> Arc<ArcSwap<TransactionDenyConfig>> {
+        self.transaction_deny_config.clone()
+    }
+
     /// This is a private method and should be kept that way. It doesn't check whether
     /// the provided transaction is a system transaction, and hence can only be called internally.
     #[instrument(level = "trace", skip_all)]
@@ -708,6 +725,9 @@ impl AuthorityState {
 
         let input_object_kinds = tx_data.input_objects()?;
         let receiving_objects_refs = tx_data.receiving_objects();
+        // Use one snapshot of the deny config for the whole transaction, even if it is swapped
+        // part way through.
+        let transaction_deny_config = self.transaction_deny_config.load_full();
 
         // Note: the deny checks may do redundant package loads but:
         // - they only load packages when there is an active package deny map
@@ -717,7 +737,7 @@ impl AuthorityState {
             transaction.tx_signatures(),
             &input_object_kinds,
             &receiving_objects_refs,
-            &self.transaction_deny_config,
+            &transaction_deny_config,
             &self.database,
         )?;
 
@@ -732,6 +752,12 @@ impl AuthorityState {
             )
             .await?;
 
+        sui_transaction_checks::deny::check_object_types_for_signing(
+            &input_objects,
+            &receiving_objects,
+            &transaction_deny_config,
+        )?;
+
         let (_gas_status, checked_input_objects) = sui_transaction_checks::check_transaction_input(
             epoch_store.protocol_config(),
             epoch_store.reference_gas_price(),
@@ -739,8 +765,17 @@ impl AuthorityState {
             input_objects,
             receiving_objects,
             &self.metrics.bytecode_verifier_metrics,
+            &self.transaction_check_config,
         )?;
 
+        if sui_transaction_checks::exceeds_input_object_warn_threshold(
+            checked_input_objects.inner(),
+            epoch_store.protocol_config(),
+            self.transaction_check_config.input_object_warn_threshold,
+        ) {
+            self.metrics.tx_input_objects_above_warn_threshold.inc();
+        }
+
         let owned_objects = checked_input_objects.inner().filter_owned_objects();
 
         let signed_transaction = VerifiedSignedTransaction::new(
@@ -1409,13 +1444,14 @@ impl AuthorityState {
 
         let input_object_kinds = transaction.input_objects()?;
         let receiving_object_refs = transaction.receiving_objects();
+        let transaction_deny_config = self.transaction_deny_config.load_full();
 
         sui_transaction_checks::deny::check_transaction_for_signing(
             &transaction,
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.transaction_deny_config,
+            &transaction_deny_config,
             &self.database,
         )?;
 
@@ -1429,6 +1465,12 @@ impl AuthorityState {
             )
             .await?;
 
+        sui_transaction_checks::deny::check_object_types_for_signing(
+            &input_objects,
+            &receiving_objects,
+            &transaction_deny_config,
+        )?;
+
         // make a gas object if one was not provided
         let mut gas_object_refs = transaction.gas().to_vec();
         let ((gas_status, checked_input_objects), mock_gas) = if transaction.gas().is_empty() {
@@ -1454,6 +1496,7 @@ impl AuthorityState {
                     receiving_objects,
                     gas_object,
                     &self.metrics.bytecode_verifier_metrics,
+                    &self.transaction_check_config,
                 )?,
                 Some(gas_object_id),
             )
@@ -1466,6 +1509,7 @@ impl AuthorityState {
                     input_objects,
                     receiving_objects,
                     &self.metrics.bytecode_verifier_metrics,
+                    &self.transaction_check_config,
                 )?,
                 None,
             )
@@ -1618,6 +1662,7 @@ impl AuthorityState {
                 input_objects,
                 receiving_objects,
                 gas_object,
+                None, /* gas_owner */
             )?;
 
         let gas_budget = max_tx_gas;
@@ -2140,6 +2185,7 @@ impl AuthorityState {
         expensive_safety_check_config: ExpensiveSafetyCheckConfig,
         transaction_deny_config: TransactionDenyConfig,
         certificate_deny_config: CertificateDenyConfig,
+        transaction_check_config: TransactionCheckConfig,
         indirect_objects_threshold: usize,
         debug_dump_config: StateDebugDumpConfig,
         overload_threshold_config: OverloadThresholdConfig,
@@ -2187,8 +2233,9 @@ impl AuthorityState {
             _authority_per_epoch_pruner,
             db_checkpoint_config: db_checkpoint_config.clone(),
             expensive_safety_check_config,
-            transaction_deny_config,
+            transaction_deny_config: Arc::new(ArcSwap::from_pointee(transaction_deny_config)),
             certificate_deny_config,
+            transaction_check_config,
             debug_dump_config,
             overload_threshold_config,
         });
@@ -18,6 +18,7 @@ use futures::stream::FuturesUnordered;
 use move_core_types::resolver::ModuleResolver;
 use serde::{Deserialize, Serialize};
 use sui_storage::mutex_table::{MutexGuard, MutexTable, RwLockGuard, RwLockTable};
+use sui_transaction_checks::ReceivingObjectStore;
 use sui_types::accumulator::Accumulator;
 use sui_types::digests::TransactionEventsDigest;
 use sui_types::error::UserInputError;
@@ -618,6 +619,23 @@ impl AuthorityStore {
             .is_some_and(|marker_value| marker_value == MarkerValue::Received))
     }
 
+    /// Batched form of `have_received_object_at_version`, resolving all keys with a single
+    /// marker table read.
+    pub fn multi_have_received_object_at_version(
+        &self,
+        object_keys: &[ObjectKey],
+        epoch_id: EpochId,
+    ) -> Result<Vec<bool>, SuiError> {
+        let marker_keys = object_keys.iter().map(|key| (epoch_id, *key));
+        Ok(self
+            .perpetual_tables
+            .object_per_epoch_marker_table
+            .multi_get(marker_keys)?
+            .into_iter()
+            .map(|marker_value| marker_value == Some(MarkerValue::Received))
+            .collect())
+    }
+
     pub fn have_deleted_owned_object_at_version_or_after(
         &self,
         object_id: &ObjectID,
@@ -2029,6 +2047,20 @@ impl ObjectStore for AuthorityStore {
     }
 }
 
+impl ReceivingObjectStore for AuthorityStore {
+    fn multi_have_received_object_at_version(
+        &self,
+        object_keys: &[ObjectKey],
+        epoch_id: EpochId,
+    ) -> SuiResult<Vec<bool>> {
+        AuthorityStore::multi_have_received_object_at_version(self, object_keys, epoch_id)
+    }
+
+    fn multi_get_objects(&self, object_ids: &[ObjectID]) -> SuiResult<Vec<Option<Object>>> {
+        self.get_objects(object_ids)
+    }
+}
+
 impl ChildObjectResolver for AuthorityStore {
     fn read_child_object(
         &self,
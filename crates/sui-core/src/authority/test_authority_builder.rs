@@ -21,6 +21,7 @@ use sui_config::node::{
     AuthorityStorePruningConfig, DBCheckpointConfig, ExpensiveSafetyCheckConfig,
 };
 use sui_config::node::{OverloadThresholdConfig, StateDebugDumpConfig};
+use sui_config::transaction_check_config::TransactionCheckConfig;
 use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_macros::nondeterministic;
 use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
@@ -271,6 +272,7 @@ impl<'a> TestAuthorityBuilder<'a> {
             ExpensiveSafetyCheckConfig::new_enable_all(),
             transaction_deny_config,
             certificate_deny_config,
+            TransactionCheckConfig::default(),
             usize::MAX,
             StateDebugDumpConfig {
                 dump_file_directory: Some(tempdir().unwrap().into_path()),
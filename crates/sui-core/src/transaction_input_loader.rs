@@ -13,8 +13,7 @@ use sui_types::{
     fp_ensure,
     storage::{BackingPackageStore, GetSharedLocks, ObjectKey, ObjectStore},
     transaction::{
-        InputObjectKind, InputObjects, ObjectReadResult, ObjectReadResultKind,
-        ReceivingObjectReadResult, ReceivingObjectReadResultKind, ReceivingObjects,
+        InputObjectKind, InputObjects, ObjectReadResult, ObjectReadResultKind, ReceivingObjects,
     },
 };
 use tracing::instrument;
@@ -331,32 +330,57 @@ impl TransactionInputLoader {
         receiving_objects: &[ObjectRef],
         epoch_id: EpochId,
     ) -> SuiResult<ReceivingObjects> {
-        let mut receiving_results = Vec::with_capacity(receiving_objects.len());
-        for objref in receiving_objects {
-            // Note: the digest is checked later in check_transaction_input
-            let (object_id, version, _) = objref;
+        // Note: the digests are checked later in check_transaction_input
+        sui_transaction_checks::load_receiving_objects(
+            self.store.as_ref(),
+            receiving_objects,
+            epoch_id,
+        )
+    }
+}
 
-            if self
-                .store
-                .have_received_object_at_version(object_id, *version, epoch_id)?
-            {
-                receiving_results.push(ReceivingObjectReadResult::new(
-                    *objref,
-                    ReceivingObjectReadResultKind::PreviouslyReceivedObject,
-                ));
-                continue;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authority::test_authority_builder::TestAuthorityBuilder;
+    use sui_types::base_types::SuiAddress;
+    use sui_types::object::Object;
 
-            let Some(object) = self.store.get_object(object_id)? else {
-                return Err(UserInputError::ObjectNotFound {
-                    object_id: *object_id,
-                    version: Some(*version),
-                }
-                .into());
-            };
+    #[tokio::test]
+    async fn test_read_receiving_objects_batched() {
+        let state = TestAuthorityBuilder::new().build().await;
+        let owner = SuiAddress::random_for_testing_only();
+        let objects: Vec<_> = (0..50)
+            .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), owner))
+            .collect();
+        state.insert_genesis_objects(&objects).await;
+        let loader = TransactionInputLoader::new(state.database.clone());
 
-            receiving_results.push(ReceivingObjectReadResult::new(*objref, object.into()));
+        let refs: Vec<_> = objects
+            .iter()
+            .map(|o| o.compute_object_reference())
+            .collect();
+        let results = loader.read_receiving_objects(&refs, 0).unwrap();
+        assert_eq!(results.iter().count(), 50);
+        for (result, objref) in results.iter().zip(&refs) {
+            assert_eq!(result.object_ref, *objref);
+            assert!(!result.is_previously_received());
         }
-        Ok(receiving_results.into())
+
+        // The first missing object in input order is the one reported.
+        let mut with_missing = refs.clone();
+        let first_missing = (ObjectID::random(), SequenceNumber::from(3), refs[0].2);
+        let second_missing = (ObjectID::random(), SequenceNumber::from(4), refs[0].2);
+        with_missing.insert(10, first_missing);
+        with_missing.insert(20, second_missing);
+        let err = loader.read_receiving_objects(&with_missing, 0).unwrap_err();
+        assert_eq!(
+            err,
+            UserInputError::ObjectNotFound {
+                object_id: first_missing.0,
+                version: Some(first_missing.1),
+            }
+            .into()
+        );
     }
 }
@@ -9,19 +9,26 @@ use crate::authority::AuthorityState;
 use crate::test_utils::make_transfer_sui_transaction;
 use fastcrypto::ed25519::Ed25519KeyPair;
 use fastcrypto::traits::KeyPair;
+use move_core_types::account_address::AccountAddress;
 use move_core_types::ident_str;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use std::path::PathBuf;
 use std::sync::Arc;
 use sui_config::certificate_deny_config::CertificateDenyConfigBuilder;
-use sui_config::transaction_deny_config::{TransactionDenyConfig, TransactionDenyConfigBuilder};
+use sui_config::transaction_deny_config::{
+    DeniedMoveFunction, TransactionDenyConfig, TransactionDenyConfigBuilder, TransactionKindTag,
+};
 use sui_swarm_config::genesis_config::{AccountConfig, DEFAULT_GAS_AMOUNT};
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_test_transaction_builder::TestTransactionBuilder;
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_types::coin::Coin;
 use sui_types::effects::TransactionEffectsAPI;
 use sui_types::error::{SuiError, SuiResult, UserInputError};
 use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus};
+use sui_types::gas_coin::GasCoin;
 use sui_types::messages_grpc::HandleTransactionResponse;
+use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{
     CallArg, CertifiedTransaction, Transaction, TransactionData, VerifiedCertificate,
     VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
@@ -30,6 +37,7 @@ use sui_types::utils::get_zklogin_user_address;
 use sui_types::utils::{
     make_zklogin_tx, to_sender_signed_transaction, to_sender_signed_transaction_with_multi_signers,
 };
+use sui_types::SUI_SYSTEM_PACKAGE_ID;
 
 const ACCOUNT_NUM: usize = 5;
 const GAS_OBJECT_COUNT: usize = 15;
@@ -131,6 +139,20 @@ async fn transfer_with_account(
         .await
 }
 
+async fn stake_with_account(
+    account: &Account,
+    state: &Arc<AuthorityState>,
+) -> SuiResult<HandleTransactionResponse> {
+    let gas_price = state.reference_gas_price_for_testing().unwrap();
+    let tx = TestTransactionBuilder::new(account.0, account.2[0], gas_price)
+        .call_staking(account.2[1], SuiAddress::default())
+        .build_and_sign(&account.1);
+    let tx = state.verify_transaction(tx).unwrap();
+    state
+        .handle_transaction(&state.epoch_store_for_testing(), tx)
+        .await
+}
+
 async fn handle_move_call_transaction(
     state: &Arc<AuthorityState>,
     package: ObjectID,
@@ -242,6 +264,24 @@ async fn test_signer_denied() {
     assert_denied(&transfer_with_account(&accounts[2], &accounts[1], &state).await);
 }
 
+#[tokio::test]
+async fn test_deny_config_reload() {
+    let (network_config, state) = setup_test(TransactionDenyConfigBuilder::new().build()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    transfer_with_account(&accounts[0], &accounts[0], &state)
+        .await
+        .unwrap();
+
+    // Swap in a config denying the sender, without rebuilding the authority state.
+    state.transaction_deny_config_handle().store(Arc::new(
+        TransactionDenyConfigBuilder::new()
+            .add_denied_address(accounts[0].0)
+            .build(),
+    ));
+    // Sponsor with a different account so that this is not the transaction signed above.
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[1], &state).await);
+}
+
 #[tokio::test]
 async fn test_shared_object_transaction_disabled() {
     let (network_config, state) = setup_test(
@@ -286,6 +326,116 @@ async fn test_package_publish_disabled() {
     assert_denied(&result);
 }
 
+#[tokio::test]
+async fn test_transaction_kind_denied() {
+    let (network_config, state) = setup_test(
+        TransactionDenyConfigBuilder::new()
+            .add_denied_transaction_kind(TransactionKindTag::Publish)
+            .build(),
+    )
+    .await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    let rgp = state.reference_gas_price_for_testing().unwrap();
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("src/unit_tests/data/object_basics");
+    let (sender, keypair, gas_object) = (accounts[0].0, &accounts[0].1, accounts[0].2[0]);
+    let tx = TestTransactionBuilder::new(sender, gas_object, rgp)
+        .publish(path)
+        .build_and_sign(keypair);
+    let tx = state.verify_transaction(tx).unwrap();
+    let result = state
+        .handle_transaction(&state.epoch_store_for_testing(), tx)
+        .await;
+    assert_denied(&result);
+
+    // Transfers are still allowed.
+    assert!(transfer_with_account(&accounts[1], &accounts[1], &state)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_object_type_denied() {
+    let (network_config, state) = setup_test(
+        TransactionDenyConfigBuilder::new()
+            .add_denied_type(GasCoin::type_())
+            .build(),
+    )
+    .await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    // The gas coin is a Coin<SUI>.
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+
+    // Denying coins of another type leaves Coin<SUI> alone.
+    let other_coin = Coin::type_(TypeTag::Struct(Box::new(StructTag {
+        address: AccountAddress::ONE,
+        module: ident_str!("other").to_owned(),
+        name: ident_str!("OTHER").to_owned(),
+        type_params: vec![],
+    })));
+    let state = reload_state_with_new_deny_config(
+        &network_config,
+        state,
+        TransactionDenyConfigBuilder::new()
+            .add_denied_type(other_coin)
+            .build(),
+    )
+    .await;
+    assert!(transfer_with_account(&accounts[1], &accounts[1], &state)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_function_denied() {
+    let (network_config, state) = setup_test(
+        TransactionDenyConfigBuilder::new()
+            .add_denied_function(
+                SUI_SYSTEM_PACKAGE_ID,
+                SUI_SYSTEM_MODULE_NAME.as_str(),
+                "request_add_stake",
+            )
+            .build(),
+    )
+    .await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    assert_denied(&stake_with_account(&accounts[0], &state).await);
+    // Transactions that don't call the function are still allowed.
+    assert!(transfer_with_account(&accounts[1], &accounts[1], &state)
+        .await
+        .is_ok());
+
+    // Denying every function in the module.
+    let state = reload_state_with_new_deny_config(
+        &network_config,
+        state,
+        TransactionDenyConfigBuilder::new()
+            .add_denied_function(
+                SUI_SYSTEM_PACKAGE_ID,
+                SUI_SYSTEM_MODULE_NAME.as_str(),
+                DeniedMoveFunction::ANY_FUNCTION,
+            )
+            .build(),
+    )
+    .await;
+    assert_denied(&stake_with_account(&accounts[2], &state).await);
+
+    // Denying a different function in the module leaves staking alone.
+    let state = reload_state_with_new_deny_config(
+        &network_config,
+        state,
+        TransactionDenyConfigBuilder::new()
+            .add_denied_function(
+                SUI_SYSTEM_PACKAGE_ID,
+                SUI_SYSTEM_MODULE_NAME.as_str(),
+                "request_withdraw_stake",
+            )
+            .build(),
+    )
+    .await;
+    assert!(stake_with_account(&accounts[3], &state).await.is_ok());
+}
+
 #[tokio::test]
 async fn test_package_denied() {
     let (network_config, state) = setup_test(TransactionDenyConfigBuilder::new().build()).await;
@@ -5,12 +5,19 @@ use move_bytecode_verifier::meter::Scope;
 use prometheus::Registry;
 use std::{path::PathBuf, sync::Arc, time::Instant};
 use sui_adapter::adapter::{default_verifier_config, run_metered_move_bytecode_verifier};
+use sui_config::transaction_check_config::TransactionCheckConfig;
 use sui_framework::BuiltInFramework;
 use sui_move_build::{CompiledPackage, SuiPackageHooks};
 use sui_protocol_config::ProtocolConfig;
+use sui_transaction_checks::{
+    check_non_system_packages_to_be_published, check_packages_with_per_package_meter,
+};
 use sui_types::{
-    error::{SuiError, SuiResult},
+    base_types::{random_object_ref, SuiAddress},
+    error::{SuiError, SuiResult, UserInputError},
     metrics::BytecodeVerifierMetrics,
+    transaction::{Command, ProgrammableTransaction, TransactionData},
+    SUI_FRAMEWORK_PACKAGE_ID,
 };
 use sui_verifier::meter::SuiVerifierMeter;
 
@@ -305,6 +312,15 @@ fn test_meter_system_packages() {
             ])
             .get()
     );
+
+    // The peak usage survives scope resets, and stays below the limit since nothing timed out
+    assert!(meter.get_peak_usage(Scope::Function) >= meter.get_usage(Scope::Function));
+    assert!(meter.get_peak_usage(Scope::Module) >= meter.get_usage(Scope::Module));
+    let fraction = meter.peak_usage_fraction().unwrap();
+    assert!(
+        fraction > 0.0 && fraction < 1.0,
+        "unexpected fraction {fraction}"
+    );
 }
 
 #[test]
@@ -350,3 +366,232 @@ fn test_build_and_verify_programmability_examples() {
         });
     }
 }
+
+#[test]
+#[cfg_attr(msim, ignore)]
+fn test_per_package_verifier_meter() {
+    let framework = BuiltInFramework::get_package_by_id(&SUI_FRAMEWORK_PACKAGE_ID);
+    let transaction = TransactionData::new_programmable(
+        SuiAddress::random_for_testing_only(),
+        vec![random_object_ref()],
+        ProgrammableTransaction {
+            inputs: vec![],
+            commands: vec![
+                Command::MakeMoveVec(None, vec![]),
+                Command::Publish(
+                    framework.bytes().to_vec(),
+                    framework.dependencies().to_vec(),
+                ),
+            ],
+        },
+        1_000_000_000,
+        1_000,
+    );
+
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    protocol_config.set_per_package_verifier_meter_for_testing(true);
+    let registry = &Registry::new();
+    let bytecode_verifier_metrics = Arc::new(BytecodeVerifierMetrics::new(registry));
+
+    check_non_system_packages_to_be_published(
+        &transaction,
+        &protocol_config,
+        &bytecode_verifier_metrics,
+        &TransactionCheckConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        1,
+        bytecode_verifier_metrics
+            .verifier_runtime_per_package_success_latency
+            .get_sample_count(),
+    );
+    // The shared meter is not used at all
+    assert_eq!(
+        0,
+        bytecode_verifier_metrics
+            .verifier_runtime_per_ptb_success_latency
+            .get_sample_count(),
+    );
+
+    // Starve the meter, the timeout names the publish command
+    protocol_config.set_max_verifier_meter_ticks_per_function_for_testing(1);
+    protocol_config.set_max_meter_ticks_per_module_for_testing(1);
+    let err = check_non_system_packages_to_be_published(
+        &transaction,
+        &protocol_config,
+        &bytecode_verifier_metrics,
+        &TransactionCheckConfig::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        UserInputError::PackageVerificationTimedoutInCommand {
+            command_index: 1,
+            ..
+        }
+    ));
+    assert_eq!(
+        1,
+        bytecode_verifier_metrics
+            .verifier_runtime_per_package_timeout_latency
+            .get_sample_count(),
+    );
+}
+
+/// Publishes `copies` copies of the Sui framework, the largest system package.
+fn publish_framework_copies(copies: usize) -> ProgrammableTransaction {
+    let framework = BuiltInFramework::get_package_by_id(&SUI_FRAMEWORK_PACKAGE_ID);
+    ProgrammableTransaction {
+        inputs: vec![],
+        commands: (0..copies)
+            .map(|_| {
+                Command::Publish(
+                    framework.bytes().to_vec(),
+                    framework.dependencies().to_vec(),
+                )
+            })
+            .collect(),
+    }
+}
+
+#[test]
+#[cfg_attr(msim, ignore)]
+fn test_parallel_per_package_verifier_meter() {
+    let publish_frameworks = publish_framework_copies(4);
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let registry = &Registry::new();
+    let bytecode_verifier_metrics = Arc::new(BytecodeVerifierMetrics::new(registry));
+
+    let check = |parallel| {
+        check_packages_with_per_package_meter(
+            &publish_frameworks,
+            &protocol_config,
+            &bytecode_verifier_metrics,
+            &TransactionCheckConfig::default(),
+            parallel,
+        )
+    };
+    let usage = || {
+        let usage_fraction = &bytecode_verifier_metrics.verifier_meter_usage_fraction;
+        (
+            usage_fraction.get_sample_count(),
+            usage_fraction.get_sample_sum(),
+        )
+    };
+
+    // Both modes meter every package against the same budget, and use the same share of it
+    check(false).unwrap();
+    let (sequential_count, sequential_sum) = usage();
+    check(true).unwrap();
+    let (total_count, total_sum) = usage();
+    assert_eq!(publish_frameworks.commands.len() as u64, sequential_count);
+    assert_eq!(sequential_count, total_count - sequential_count);
+    assert!(
+        (total_sum - 2.0 * sequential_sum).abs() < 1e-9,
+        "sequential usage {sequential_sum}, total usage {total_sum}"
+    );
+
+    // Every package is metered once per run, and only the parallel run records the PTB
+    // wall-clock time
+    assert_eq!(
+        2 * publish_frameworks.commands.len() as u64,
+        bytecode_verifier_metrics
+            .verifier_runtime_per_package_success_latency
+            .get_sample_count(),
+    );
+    assert_eq!(
+        1,
+        bytecode_verifier_metrics
+            .verifier_runtime_per_ptb_success_latency
+            .get_sample_count(),
+    );
+
+    // When every package times out, both modes report the first command
+    protocol_config.set_max_verifier_meter_ticks_per_function_for_testing(1);
+    protocol_config.set_max_meter_ticks_per_module_for_testing(1);
+    for parallel in [false, true] {
+        let err = check_packages_with_per_package_meter(
+            &publish_frameworks,
+            &protocol_config,
+            &bytecode_verifier_metrics,
+            &TransactionCheckConfig::default(),
+            parallel,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            UserInputError::PackageVerificationTimedoutInCommand {
+                command_index: 0,
+                ..
+            }
+        ));
+    }
+    assert_eq!(
+        1,
+        bytecode_verifier_metrics
+            .verifier_runtime_per_ptb_timeout_latency
+            .get_sample_count(),
+    );
+}
+
+#[test]
+#[cfg_attr(msim, ignore)]
+fn test_parallel_shared_verifier_budget() {
+    let copies = 4;
+    let transaction = TransactionData::new_programmable(
+        SuiAddress::random_for_testing_only(),
+        vec![random_object_ref()],
+        publish_framework_copies(copies),
+        1_000_000_000,
+        1_000,
+    );
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    protocol_config.set_per_package_verifier_meter_for_testing(false);
+    let registry = &Registry::new();
+    let bytecode_verifier_metrics = Arc::new(BytecodeVerifierMetrics::new(registry));
+
+    // The module scope is never re-entered, so the shared meter's module ticks add up across
+    // every copy
+    let verifier_config = default_verifier_config(&protocol_config, true /* is_metered */);
+    let mut meter = SuiVerifierMeter::new(&verifier_config);
+    run_metered_move_bytecode_verifier(
+        &BuiltInFramework::get_package_by_id(&SUI_FRAMEWORK_PACKAGE_ID).modules(),
+        &verifier_config,
+        &mut meter,
+        &bytecode_verifier_metrics,
+    )
+    .unwrap();
+    let total_module_ticks = copies as u64 * meter.get_usage(Scope::Module) as u64;
+
+    // Both modes reject a budget that the copies reach together, though any one copy fits in it,
+    // and accept one just above it
+    for (max_module_ticks, accepted) in
+        [(total_module_ticks, false), (total_module_ticks + 1, true)]
+    {
+        protocol_config.set_max_meter_ticks_per_module_for_testing(max_module_ticks);
+        for parallel_package_verification in [false, true] {
+            let check_config = TransactionCheckConfig {
+                parallel_package_verification,
+                ..Default::default()
+            };
+            let result = check_non_system_packages_to_be_published(
+                &transaction,
+                &protocol_config,
+                &bytecode_verifier_metrics,
+                &check_config,
+            );
+            assert_eq!(
+                accepted,
+                result.is_ok(),
+                "parallel: {parallel_package_verification}, result: {result:?}"
+            );
+            if let Err(err) = result {
+                assert!(matches!(
+                    err,
+                    UserInputError::PackageVerificationTimedout { .. }
+                ));
+            }
+        }
+    }
+}
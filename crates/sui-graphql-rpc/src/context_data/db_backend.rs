@@ -3,7 +3,7 @@
 
 use diesel::backend::Backend;
 use sui_indexer::{
-    schema_v2::{checkpoints, epochs, objects, transactions},
+    schema_v2::{checkpoints, epochs, events, objects, packages, transactions},
     types_v2::OwnerType,
 };
 
@@ -13,7 +13,7 @@ use crate::{
 };
 use diesel::{
     query_builder::{BoxedSelectStatement, FromClause, QueryId},
-    sql_types::Text,
+    sql_types::{Bytea, Text},
 };
 
 pub(crate) type BalanceQuery<'a, DB> = BoxedSelectStatement<
@@ -38,6 +38,10 @@ pub(crate) trait GenericQueryBuilder<DB: Backend> {
         sequence_number: i64,
     ) -> checkpoints::BoxedQuery<'static, DB>;
     fn get_latest_checkpoint() -> checkpoints::BoxedQuery<'static, DB>;
+    fn get_latest_package_version(original_id: Vec<u8>) -> packages::BoxedQuery<'static, DB>;
+    fn get_checkpoint_tx_digests(
+        checkpoint_sequence_number: i64,
+    ) -> transactions::BoxedQuery<'static, DB, Bytea>;
     fn multi_get_txs(
         cursor: Option<i64>,
         descending_order: bool,
@@ -68,6 +72,11 @@ pub(crate) trait GenericQueryBuilder<DB: Backend> {
         limit: i64,
         epoch: Option<i64>,
     ) -> checkpoints::BoxedQuery<'static, DB>;
+    fn get_events_by_type(
+        event_type: String,
+        cursor: Option<(i64, i64)>,
+        limit: i64,
+    ) -> events::BoxedQuery<'static, DB>;
 }
 
 /// Struct for custom diesel function
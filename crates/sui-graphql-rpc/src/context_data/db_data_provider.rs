@@ -47,13 +47,13 @@ use crate::{
 use async_graphql::connection::{Connection, Edge};
 use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
 use move_core_types::language_storage::StructTag;
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 use sui_indexer::{
     apis::GovernanceReadApiV2,
     indexer_reader::IndexerReader,
     models_v2::{
-        checkpoints::StoredCheckpoint, epoch::StoredEpochInfo, objects::StoredObject,
-        transactions::StoredTransaction,
+        checkpoints::StoredCheckpoint, epoch::StoredEpochInfo, events::StoredEvent,
+        objects::StoredObject, packages::StoredPackage, transactions::StoredTransaction,
     },
     schema_v2::transactions,
     types_v2::OwnerType,
@@ -64,7 +64,7 @@ use sui_json_rpc::{
     name_service::{Domain, NameRecord, NameServiceConfig},
 };
 use sui_json_rpc_types::{
-    EventFilter as RpcEventFilter, ProtocolConfigResponse, Stake as RpcStakedSui,
+    EventFilter as RpcEventFilter, Page, ProtocolConfigResponse, Stake as RpcStakedSui,
     SuiTransactionBlockEffects,
 };
 use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
@@ -89,8 +89,10 @@ use sui_types::{
     },
     Identifier,
 };
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::warn;
 
-use super::{db_backend::GenericQueryBuilder, DEFAULT_PAGE_SIZE};
+use super::{db_backend::GenericQueryBuilder, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
 
 #[cfg(feature = "pg_backend")]
 use super::pg_backend::{PgQueryExecutor, QueryBuilder};
@@ -119,6 +121,8 @@ pub enum DbValidationError {
     InvalidOwnerType,
     #[error("Query cost exceeded - cost: {0}, limit: {1}")]
     QueryCostExceeded(u64, u64),
+    #[error("Page size {0} is out of range, must be between 1 and {1}")]
+    InvalidPageSize(i64, u64),
 }
 
 pub(crate) struct PgManager {
@@ -138,6 +142,29 @@ impl PgManager {
         IndexerReader::new_with_config(db_url, config)
             .map_err(|e| Error::Internal(format!("Failed to create reader: {e}")))
     }
+
+    /// Spawns a background task that pings `connections` pooled connections every `period`, so
+    /// that they are warm when traffic picks up after a quiet spell. At least as many connections
+    /// as the pool's `min_idle` (none if it is unset) are pinged, and always fewer than the pool
+    /// size.
+    ///
+    /// Overhead: one `SELECT 1` round trip per pinged connection per period. The connections are
+    /// pinged one after the other, and those already pinged stay checked out until the last ping
+    /// is done, so a period should be well above the time it takes to ping them.
+    pub(crate) fn start_keepalive(&self, period: Duration, connections: u32) -> JoinHandle<()> {
+        let reader = self.inner.clone();
+        let connections = connections.max(reader.min_idle().unwrap_or(0));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = reader.ping_connections(connections).await {
+                    warn!("Failed to ping pooled connections: {e}");
+                }
+            }
+        })
+    }
 }
 
 /// Implement methods to query db and return StoredData
@@ -162,6 +189,36 @@ impl PgManager {
         .await
     }
 
+    async fn get_latest_package_version(
+        &self,
+        original_id: Vec<u8>,
+    ) -> Result<Option<StoredPackage>, Error> {
+        self.run_query_async_with_cost(
+            move || {
+                Ok(QueryBuilder::get_latest_package_version(
+                    original_id.clone(),
+                ))
+            },
+            |query| move |conn| query.get_result::<StoredPackage>(conn).optional(),
+        )
+        .await
+    }
+
+    async fn get_checkpoint_tx_digests(
+        &self,
+        checkpoint_sequence_number: i64,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        self.run_query_async_with_cost(
+            move || {
+                Ok(QueryBuilder::get_checkpoint_tx_digests(
+                    checkpoint_sequence_number,
+                ))
+            },
+            |query| move |conn| query.load::<Vec<u8>>(conn),
+        )
+        .await
+    }
+
     pub async fn get_epoch(&self, epoch_id: Option<i64>) -> Result<Option<StoredEpochInfo>, Error> {
         let query_fn = move || {
             Ok(match epoch_id {
@@ -735,6 +792,46 @@ impl PgManager {
         })?))
     }
 
+    /// Resolves the ID of the latest version of the package whose upgrade lineage starts at
+    /// `original_id`. A package that has never been upgraded resolves to itself.
+    pub(crate) async fn latest_package_version(
+        &self,
+        original_id: ObjectID,
+    ) -> Result<Option<ObjectID>, Error> {
+        let Some(stored_package) = self
+            .get_latest_package_version(original_id.to_vec())
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        ObjectID::from_bytes(stored_package.package_id)
+            .map(Some)
+            .map_err(|e| Error::Internal(format!("Failed to deserialize package id: {e}")))
+    }
+
+    /// Digests of the transactions in checkpoint `sequence_number`, in execution order. A
+    /// checkpoint that has not been indexed (yet) has no transactions, so it yields an empty list
+    /// rather than an error.
+    pub(crate) async fn checkpoint_transaction_digests(
+        &self,
+        sequence_number: u64,
+    ) -> Result<Vec<TransactionDigest>, Error> {
+        let sequence_number = i64::try_from(sequence_number).map_err(|_| {
+            Error::Internal("Failed to convert checkpoint sequence number to i64".to_string())
+        })?;
+
+        self.get_checkpoint_tx_digests(sequence_number)
+            .await?
+            .into_iter()
+            .map(|digest| {
+                TransactionDigest::try_from(digest.as_slice()).map_err(|e| {
+                    Error::Internal(format!("Failed to deserialize transaction digest: {e}"))
+                })
+            })
+            .collect()
+    }
+
     pub(crate) async fn fetch_owned_objs(
         &self,
         first: Option<u64>,
@@ -1136,6 +1233,105 @@ impl PgManager {
         Ok(stake)
     }
 
+    /// Paginates through events of type `event_type` in the order they were emitted, starting
+    /// after the `(tx_sequence_number, event_sequence_number)` cursor `after`, if provided.
+    pub(crate) async fn events_by_type(
+        &self,
+        event_type: &str,
+        after: Option<(i64, i64)>,
+        limit: i64,
+    ) -> Result<Page<StoredEvent, (i64, i64)>, Error> {
+        if limit <= 0 || limit as u64 > MAX_PAGE_SIZE {
+            return Err(DbValidationError::InvalidPageSize(limit, MAX_PAGE_SIZE).into());
+        }
+        // Events are stored under the canonical form of their type.
+        let event_type = StructTag::from_str(event_type)
+            .map_err(|_| Error::InvalidFilter)?
+            .to_canonical_string(/* with_prefix */ true);
+
+        let mut events: Vec<StoredEvent> = self
+            .run_query_async_with_cost(
+                move || {
+                    Ok(QueryBuilder::get_events_by_type(
+                        event_type.clone(),
+                        after,
+                        limit,
+                    ))
+                },
+                |query| move |conn| query.load(conn),
+            )
+            .await?;
+
+        let has_next_page = events.len() as i64 > limit;
+        if has_next_page {
+            events.pop();
+        }
+        let next_cursor = events
+            .last()
+            .map(|e| (e.tx_sequence_number, e.event_sequence_number));
+
+        Ok(Page {
+            data: events,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    /// Paginates through the objects owned by address `owner` in object id order, starting after
+    /// the object id `after`, if provided. With a `type_filter`, only objects of exactly that type
+    /// are returned.
+    pub(crate) async fn owned_objects(
+        &self,
+        owner: SuiAddress,
+        type_filter: Option<&str>,
+        after: Option<Vec<u8>>,
+        limit: i64,
+    ) -> Result<Page<StoredObject, Vec<u8>>, Error> {
+        if limit <= 0 || limit as u64 > MAX_PAGE_SIZE {
+            return Err(DbValidationError::InvalidPageSize(limit, MAX_PAGE_SIZE).into());
+        }
+        // Object types are stored in their canonical form.
+        let ty = type_filter
+            .map(|ty| {
+                StructTag::from_str(ty)
+                    .map(|tag| tag.to_canonical_string(/* with_prefix */ true))
+                    .map_err(|_| Error::InvalidFilter)
+            })
+            .transpose()?;
+        let filter = ObjectFilter {
+            owner: Some(owner),
+            ty,
+            ..Default::default()
+        };
+
+        let mut objects: Vec<StoredObject> = self
+            .run_query_async_with_cost(
+                move || {
+                    QueryBuilder::multi_get_objs(
+                        after.clone(),
+                        /* descending_order */ false,
+                        limit,
+                        Some(filter.clone()),
+                        Some(OwnerType::Address),
+                    )
+                },
+                |query| move |conn| query.load(conn),
+            )
+            .await?;
+
+        let has_next_page = objects.len() as i64 > limit;
+        if has_next_page {
+            objects.pop();
+        }
+        let next_cursor = objects.last().map(|o| o.object_id.clone());
+
+        Ok(Page {
+            data: objects,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
     pub(crate) async fn fetch_events(
         &self,
         first: Option<u64>,
@@ -1762,3 +1958,336 @@ impl From<&SuiAddress> for NativeSuiAddress {
         NativeSuiAddress::try_from(a.as_slice()).unwrap()
     }
 }
+
+#[cfg(all(test, feature = "pg_integration"))]
+mod tests {
+    use super::*;
+    use crate::config::ConnectionConfig;
+    use diesel::{dsl::sql, sql_types::Integer};
+    use serial_test::serial;
+    use std::collections::BTreeSet;
+    use sui_indexer::{
+        new_pg_connection_pool_impl,
+        schema_v2::{events, objects, packages, transactions},
+        types_v2::IndexedObject,
+        utils::reset_database,
+    };
+    use sui_types::object::Object as NativeObject;
+
+    fn stored_package(package_id: ObjectID, original_id: ObjectID, version: i64) -> StoredPackage {
+        StoredPackage {
+            package_id: package_id.to_vec(),
+            original_id: original_id.to_vec(),
+            package_version: version,
+            move_package: vec![],
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_latest_package_version() {
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let blocking_pool =
+            new_pg_connection_pool_impl(&connection_config.db_url(), Some(2)).unwrap();
+        let mut conn = blocking_pool.get().unwrap();
+        reset_database(&mut conn, true, true).unwrap();
+
+        let original = ObjectID::random();
+        let upgraded = ObjectID::random();
+        let never_upgraded = ObjectID::random();
+        diesel::insert_into(packages::table)
+            .values(vec![
+                stored_package(original, original, 1),
+                stored_package(upgraded, original, 2),
+                stored_package(never_upgraded, never_upgraded, 1),
+            ])
+            .execute(&mut conn)
+            .unwrap();
+
+        let pg_manager = PgManager::new(
+            PgManager::reader(connection_config.db_url()).unwrap(),
+            Limits::default(),
+        );
+        assert_eq!(
+            pg_manager.latest_package_version(original).await.unwrap(),
+            Some(upgraded)
+        );
+        assert_eq!(
+            pg_manager
+                .latest_package_version(never_upgraded)
+                .await
+                .unwrap(),
+            Some(never_upgraded)
+        );
+        assert_eq!(
+            pg_manager
+                .latest_package_version(ObjectID::random())
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    fn stored_event(
+        tx_sequence_number: i64,
+        event_sequence_number: i64,
+        event_type: &str,
+    ) -> StoredEvent {
+        StoredEvent {
+            tx_sequence_number,
+            event_sequence_number,
+            transaction_digest: TransactionDigest::random().into_inner().to_vec(),
+            checkpoint_sequence_number: 0,
+            senders: vec![],
+            package: ObjectID::random().to_vec(),
+            module: "m".to_string(),
+            event_type: event_type.to_string(),
+            timestamp_ms: 0,
+            bcs: vec![],
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_events_by_type() {
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let blocking_pool =
+            new_pg_connection_pool_impl(&connection_config.db_url(), Some(2)).unwrap();
+        let mut conn = blocking_pool.get().unwrap();
+        reset_database(&mut conn, true, true).unwrap();
+
+        let foo = StructTag::from_str("0x42::m::Foo")
+            .unwrap()
+            .to_canonical_string(true);
+        let bar = StructTag::from_str("0x42::m::Bar")
+            .unwrap()
+            .to_canonical_string(true);
+        diesel::insert_into(events::table)
+            .values(vec![
+                stored_event(1, 0, &foo),
+                stored_event(1, 1, &bar),
+                stored_event(2, 0, &foo),
+                stored_event(3, 0, &bar),
+                stored_event(3, 1, &foo),
+            ])
+            .execute(&mut conn)
+            .unwrap();
+
+        let pg_manager = PgManager::new(
+            PgManager::reader(connection_config.db_url()).unwrap(),
+            Limits::default(),
+        );
+
+        let page = pg_manager
+            .events_by_type("0x42::m::Foo", None, 2)
+            .await
+            .unwrap();
+        let ids = |page: &Page<StoredEvent, (i64, i64)>| {
+            page.data
+                .iter()
+                .map(|e| (e.tx_sequence_number, e.event_sequence_number))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&page), vec![(1, 0), (2, 0)]);
+        assert!(page.has_next_page);
+
+        let page = pg_manager
+            .events_by_type("0x42::m::Foo", page.next_cursor, 2)
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), vec![(3, 1)]);
+        assert!(!page.has_next_page);
+
+        let page = pg_manager
+            .events_by_type("0x42::m::Bar", None, 10)
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), vec![(1, 1), (3, 0)]);
+
+        assert!(pg_manager
+            .events_by_type("not a type", None, 10)
+            .await
+            .is_err());
+        assert!(pg_manager
+            .events_by_type("0x42::m::Foo", None, MAX_PAGE_SIZE as i64 + 1)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_owned_objects() {
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let blocking_pool =
+            new_pg_connection_pool_impl(&connection_config.db_url(), Some(2)).unwrap();
+        let mut conn = blocking_pool.get().unwrap();
+        reset_database(&mut conn, true, true).unwrap();
+
+        let owner = NativeSuiAddress::random_for_testing_only();
+        let other = NativeSuiAddress::random_for_testing_only();
+        let mut owned: Vec<_> = (0..3)
+            .map(|_| NativeObject::with_owner_for_testing(owner))
+            .collect();
+        owned.sort_by_key(|o| o.id());
+        let stored = |object: &NativeObject| {
+            StoredObject::from(IndexedObject::from_object(0, object.clone(), None, None).unwrap())
+        };
+        diesel::insert_into(objects::table)
+            .values(
+                owned
+                    .iter()
+                    .chain([&NativeObject::with_owner_for_testing(other)])
+                    .map(stored)
+                    .collect::<Vec<_>>(),
+            )
+            .execute(&mut conn)
+            .unwrap();
+
+        let pg_manager = PgManager::new(
+            PgManager::reader(connection_config.db_url()).unwrap(),
+            Limits::default(),
+        );
+        let ids = |page: &Page<StoredObject, Vec<u8>>| {
+            page.data
+                .iter()
+                .map(|o| o.object_id.clone())
+                .collect::<Vec<_>>()
+        };
+        let owned_ids: Vec<_> = owned.iter().map(|o| o.id().to_vec()).collect();
+
+        // No filter, paginated.
+        let page = pg_manager
+            .owned_objects(owner.into(), None, None, 2)
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), owned_ids[..2]);
+        assert!(page.has_next_page);
+        let page = pg_manager
+            .owned_objects(owner.into(), None, page.next_cursor, 2)
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), owned_ids[2..]);
+        assert!(!page.has_next_page);
+
+        // Coin type filter.
+        let page = pg_manager
+            .owned_objects(
+                owner.into(),
+                Some("0x2::coin::Coin<0x2::sui::SUI>"),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), owned_ids);
+        let page = pg_manager
+            .owned_objects(
+                owner.into(),
+                Some("0x2::coin::Coin<0x42::usd::USD>"),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert!(page.data.is_empty());
+
+        assert!(pg_manager
+            .owned_objects(owner.into(), None, None, MAX_PAGE_SIZE as i64 + 1)
+            .await
+            .is_err());
+    }
+
+    fn stored_transaction(
+        tx_sequence_number: i64,
+        checkpoint_sequence_number: i64,
+    ) -> StoredTransaction {
+        StoredTransaction {
+            tx_sequence_number,
+            transaction_digest: TransactionDigest::random().into_inner().to_vec(),
+            raw_transaction: vec![],
+            raw_effects: vec![],
+            checkpoint_sequence_number,
+            timestamp_ms: 0,
+            object_changes: vec![],
+            balance_changes: vec![],
+            events: vec![],
+            transaction_kind: 0,
+            success_command_count: 0,
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_checkpoint_transaction_digests() {
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let blocking_pool =
+            new_pg_connection_pool_impl(&connection_config.db_url(), Some(2)).unwrap();
+        let mut conn = blocking_pool.get().unwrap();
+        reset_database(&mut conn, true, true).unwrap();
+
+        // Inserted out of order, to check that digests come back in execution order.
+        let txs = vec![
+            stored_transaction(12, 4),
+            stored_transaction(10, 4),
+            stored_transaction(13, 5),
+            stored_transaction(11, 4),
+        ];
+        diesel::insert_into(transactions::table)
+            .values(txs.clone())
+            .execute(&mut conn)
+            .unwrap();
+
+        let pg_manager = PgManager::new(
+            PgManager::reader(connection_config.db_url()).unwrap(),
+            Limits::default(),
+        );
+        let digest = |tx: &StoredTransaction| {
+            TransactionDigest::try_from(tx.transaction_digest.as_slice()).unwrap()
+        };
+        assert_eq!(
+            pg_manager.checkpoint_transaction_digests(4).await.unwrap(),
+            vec![digest(&txs[1]), digest(&txs[3]), digest(&txs[0])]
+        );
+        assert!(pg_manager
+            .checkpoint_transaction_digests(6)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_keepalive_maintains_min_idle() {
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let mut pool_config = PgConnectionPoolConfig::default();
+        pool_config.set_pool_size(4);
+        pool_config.set_min_idle(2);
+        let reader =
+            IndexerReader::new_with_config(connection_config.db_url(), pool_config).unwrap();
+        let pg_manager = PgManager::new(reader, Limits::default());
+
+        // Have the server close each idle connection after a short while, and note which backends
+        // the idle connections are on. The queries overlap, so they run on different connections.
+        let idle_backends = || async {
+            let backend = || {
+                pg_manager.inner.run_query_async(|conn| {
+                    diesel::sql_query("SET idle_session_timeout = 250").execute(conn)?;
+                    diesel::sql_query("SELECT pg_sleep(0.1)").execute(conn)?;
+                    diesel::select(sql::<Integer>("pg_backend_pid()")).get_result::<i32>(conn)
+                })
+            };
+            let (first, second) = futures::future::join(backend(), backend()).await;
+            BTreeSet::from([first.unwrap(), second.unwrap()])
+        };
+        let before = idle_backends().await;
+        assert_eq!(before.len(), 2);
+
+        // Without the pings, the server would have closed both connections by now, and the pool
+        // would have replaced them with new backends.
+        let keepalive = pg_manager.start_keepalive(Duration::from_millis(50), 1);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        keepalive.abort();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(idle_backends().await, before);
+    }
+}
@@ -9,3 +9,4 @@ pub(crate) mod package_cache;
 pub(crate) mod pg_backend;
 
 pub const DEFAULT_PAGE_SIZE: u64 = 10;
+pub const MAX_PAGE_SIZE: u64 = 50;
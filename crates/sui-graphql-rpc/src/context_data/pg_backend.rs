@@ -14,13 +14,14 @@ use async_trait::async_trait;
 use diesel::{
     pg::Pg,
     query_builder::{AstPass, QueryFragment},
+    sql_types::Bytea,
     BoolExpressionMethods, ExpressionMethods, PgConnection, QueryDsl, QueryResult, RunQueryDsl,
 };
 use std::str::FromStr;
 use sui_indexer::{
     schema_v2::{
-        checkpoints, epochs, objects, transactions, tx_calls, tx_changed_objects, tx_input_objects,
-        tx_recipients, tx_senders,
+        checkpoints, epochs, events, objects, packages, transactions, tx_calls, tx_changed_objects,
+        tx_input_objects, tx_recipients, tx_senders,
     },
     types_v2::OwnerType,
 };
@@ -71,6 +72,22 @@ impl GenericQueryBuilder<Pg> for PgQueryBuilder {
             .limit(1)
             .into_boxed()
     }
+    fn get_latest_package_version(original_id: Vec<u8>) -> packages::BoxedQuery<'static, Pg> {
+        packages::dsl::packages
+            .filter(packages::dsl::original_id.eq(original_id))
+            .order_by(packages::dsl::package_version.desc())
+            .limit(1)
+            .into_boxed()
+    }
+    fn get_checkpoint_tx_digests(
+        checkpoint_sequence_number: i64,
+    ) -> transactions::BoxedQuery<'static, Pg, Bytea> {
+        transactions::dsl::transactions
+            .select(transactions::dsl::transaction_digest)
+            .filter(transactions::dsl::checkpoint_sequence_number.eq(checkpoint_sequence_number))
+            .order_by(transactions::dsl::tx_sequence_number.asc())
+            .into_boxed()
+    }
     fn multi_get_txs(
         cursor: Option<i64>,
         descending_order: bool,
@@ -353,6 +370,32 @@ impl GenericQueryBuilder<Pg> for PgQueryBuilder {
 
         query
     }
+
+    fn get_events_by_type(
+        event_type: String,
+        cursor: Option<(i64, i64)>,
+        limit: i64,
+    ) -> events::BoxedQuery<'static, Pg> {
+        let mut query = events::dsl::events
+            .filter(events::dsl::event_type.eq(event_type))
+            .into_boxed();
+
+        if let Some((tx_sequence_number, event_sequence_number)) = cursor {
+            query = query.filter(
+                events::dsl::tx_sequence_number.gt(tx_sequence_number).or(
+                    events::dsl::tx_sequence_number
+                        .eq(tx_sequence_number)
+                        .and(events::dsl::event_sequence_number.gt(event_sequence_number)),
+                ),
+            );
+        }
+        query
+            .order((
+                events::dsl::tx_sequence_number.asc(),
+                events::dsl::event_sequence_number.asc(),
+            ))
+            .limit(limit + 1)
+    }
 }
 
 /// Allows methods like load(), get_result(), etc. on an Explained query
@@ -1,6 +1,11 @@
 CREATE TABLE packages 
 (
     package_id                   bytea          PRIMARY KEY,
+    -- ID of the first version of the package in its upgrade lineage.
+    original_id                  bytea          NOT NULL,
+    package_version              bigint         NOT NULL,
     -- bcs serialized MovePackage
     move_package                 bytea          NOT NULL
 );
+
+CREATE INDEX packages_original_id_version ON packages (original_id, package_version);
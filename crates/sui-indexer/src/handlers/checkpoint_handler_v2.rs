@@ -5,7 +5,6 @@ use crate::handlers::committer::start_tx_checkpoint_commit_task;
 use crate::handlers::tx_processor::IndexingPackageCache;
 use crate::models_v2::display::StoredDisplay;
 use async_trait::async_trait;
-use itertools::Itertools;
 use move_bytecode_utils::module_cache::GetModule;
 use mysten_metrics::{get_metrics, spawn_monitored_task};
 use std::collections::{BTreeMap, HashMap};
@@ -27,7 +26,6 @@ use sui_json_rpc_types::SuiMoveValue;
 use sui_types::base_types::SequenceNumber;
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
 use sui_types::event::SystemEpochInfoEvent;
-use sui_types::object::Owner;
 use sui_types::transaction::TransactionDataAPI;
 use tap::tap::TapFallible;
 use tracing::{error, info, warn};
@@ -44,7 +42,7 @@ use crate::store::module_resolver_v2::InterimModuleResolver;
 use crate::store::IndexerStoreV2;
 use crate::types_v2::IndexedEpochInfo;
 use crate::types_v2::{
-    IndexedCheckpoint, IndexedEvent, IndexedTransaction, IndexerResult, TransactionKind, TxIndex,
+    index_transactions, IndexedCheckpoint, IndexedEvent, IndexedTransaction, IndexerResult, TxIndex,
 };
 use crate::types_v2::{IndexedObject, IndexedPackage};
 use crate::IndexerConfig;
@@ -305,7 +303,7 @@ where
 
         // Index Objects
         let object_changes: TransactionObjectChangesToCommit =
-            Self::index_objects(data.clone(), &metrics, &module_resolver);
+            Self::index_objects(data.clone(), &metrics, &module_resolver)?;
 
         let (checkpoint, db_transactions, db_events, db_indices, db_displays) = {
             let CheckpointData {
@@ -374,10 +372,12 @@ where
             )));
         }
 
-        let mut db_transactions = Vec::new();
         let mut db_events = Vec::new();
         let mut db_displays = BTreeMap::new();
-        let mut db_indices = Vec::new();
+        let mut tx_data = Vec::with_capacity(transactions.len());
+        let mut tx_effects = Vec::with_capacity(transactions.len());
+        let mut tx_events = Vec::with_capacity(transactions.len());
+        let mut tx_changes = Vec::with_capacity(transactions.len());
 
         for tx in transactions {
             let CheckpointTransaction {
@@ -401,12 +401,6 @@ where
                 .map(|events| events.data.clone())
                 .unwrap_or_default();
 
-            let transaction_kind = if tx.is_system_tx() {
-                TransactionKind::SystemTransaction
-            } else {
-                TransactionKind::ProgrammableTransaction
-            };
-
             db_events.extend(events.iter().enumerate().map(|(idx, event)| {
                 IndexedEvent::from_event(
                     tx_sequence_number,
@@ -430,82 +424,29 @@ where
                 .chain(output_objects.iter())
                 .collect::<Vec<_>>();
 
-            let (balance_change, object_changes) =
-                TxChangesProcessor::new(&objects, metrics.clone())
-                    .get_changes(tx, &fx, &tx_digest)
-                    .await?;
-
-            let db_txn = IndexedTransaction {
-                tx_sequence_number,
-                tx_digest,
-                checkpoint_sequence_number: *checkpoint_summary.sequence_number(),
-                timestamp_ms: checkpoint_summary.timestamp_ms,
-                sender_signed_data: sender_signed_data.data().clone(),
-                effects: fx.clone(),
-                object_changes,
-                balance_change,
-                events,
-                transaction_kind,
-                successful_tx_num: if fx.status().is_ok() {
-                    tx.kind().tx_count() as u64
-                } else {
-                    0
-                },
-            };
-
-            db_transactions.push(db_txn);
-
-            // Input Objects
-            let input_objects = tx
-                .input_objects()
-                .expect("committed txns have been validated")
-                .into_iter()
-                .map(|obj_kind| obj_kind.object_id())
-                .collect::<Vec<_>>();
-
-            // Changed Objects
-            let changed_objects = fx
-                .all_changed_objects()
-                .into_iter()
-                .map(|(object_ref, _owner, _write_kind)| object_ref.0)
-                .collect::<Vec<_>>();
-
-            // Payers
-            let payers = vec![tx.gas_owner()];
-
-            // Senders
-            let senders = vec![tx.sender()];
-
-            // Recipients
-            let recipients = fx
-                .all_changed_objects()
-                .into_iter()
-                .filter_map(|(_object_ref, owner, _write_kind)| match owner {
-                    Owner::AddressOwner(address) => Some(address),
-                    _ => None,
-                })
-                .unique()
-                .collect::<Vec<_>>();
-
-            // Move Calls
-            let move_calls = tx
-                .move_calls()
-                .iter()
-                .map(|(p, m, f)| (*<&ObjectID>::clone(p), m.to_string(), f.to_string()))
-                .collect();
+            let changes = TxChangesProcessor::new(&objects, metrics.clone())
+                .get_changes(tx, &fx, &tx_digest)
+                .await?;
 
-            db_indices.push(TxIndex {
-                tx_sequence_number,
-                transaction_digest: tx_digest,
-                checkpoint_sequence_number: *checkpoint_seq,
-                input_objects,
-                changed_objects,
-                senders,
-                payers,
-                recipients,
-                move_calls,
-            });
+            tx_data.push(sender_signed_data.data().clone());
+            tx_effects.push(fx);
+            tx_events.push(events);
+            tx_changes.push(changes);
         }
+
+        let db_transactions = index_transactions(
+            *checkpoint_seq,
+            checkpoint_summary.timestamp_ms,
+            checkpoint_summary.network_total_transactions - checkpoint_contents.size() as u64,
+            tx_data,
+            tx_effects,
+            tx_events,
+            tx_changes,
+        )?;
+        let db_indices = db_transactions
+            .iter()
+            .map(IndexedTransaction::to_tx_index)
+            .collect();
         Ok((db_transactions, db_events, db_indices, db_displays))
     }
 
@@ -513,7 +454,7 @@ where
         data: CheckpointData,
         metrics: &IndexerMetrics,
         module_resolver: &impl GetModule,
-    ) -> TransactionObjectChangesToCommit {
+    ) -> IndexerResult<TransactionObjectChangesToCommit> {
         let _timer = metrics.indexing_objects_latency.start_timer();
         let checkpoint_seq = data.checkpoint_summary.sequence_number;
         let deleted_objects = data
@@ -538,6 +479,8 @@ where
                     effects: fx,
                     ..
                 } = tx;
+                let modified_at_versions: HashMap<_, _> =
+                    fx.modified_at_versions().into_iter().collect();
                 fx.all_changed_objects()
                     .into_iter()
                     .filter_map(|(oref, _owner, _kind)| {
@@ -565,19 +508,23 @@ where
                             )
                                 });
 
+                        let previous_version = modified_at_versions
+                            .get(&oref.0)
+                            .map(|version| version.value());
                         Some(IndexedObject::from_object(
                             checkpoint_seq,
                             object.clone(),
+                            previous_version,
                             df_info,
                         ))
                     })
                     .collect::<Vec<_>>()
             })
-            .collect();
-        TransactionObjectChangesToCommit {
+            .collect::<IndexerResult<Vec<_>>>()?;
+        Ok(TransactionObjectChangesToCommit {
             changed_objects,
             deleted_objects,
-        }
+        })
     }
 
     fn index_packages(
@@ -593,11 +540,10 @@ where
                     .iter()
                     .filter_map(|o| {
                         if let sui_types::object::Data::Package(p) = &o.data {
-                            Some(IndexedPackage {
-                                package_id: o.id(),
-                                move_package: p.clone(),
+                            Some(IndexedPackage::from_package(
                                 checkpoint_sequence_number,
-                            })
+                                p.clone(),
+                            ))
                         } else {
                             None
                         }
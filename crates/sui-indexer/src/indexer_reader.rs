@@ -27,8 +27,10 @@ use anyhow::{anyhow, Result};
 use cached::proc_macro::cached;
 use cached::SizedCache;
 use diesel::{
-    r2d2::ConnectionManager, ExpressionMethods, OptionalExtension, PgConnection, QueryDsl,
-    RunQueryDsl,
+    dsl::sql,
+    r2d2::ConnectionManager,
+    sql_types::{Bool, Integer},
+    ExpressionMethods, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl,
 };
 use fastcrypto::encoding::Encoding;
 use fastcrypto::encoding::Hex;
@@ -36,7 +38,10 @@ use itertools::{any, Itertools};
 use move_core_types::language_storage::StructTag;
 use std::{
     collections::{BTreeMap, HashMap},
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicBool, AtomicI32, Ordering},
+        Arc, Condvar, Mutex, RwLock,
+    },
 };
 use sui_json_rpc_types::{
     AddressMetrics, CheckpointId, EpochInfo, EventFilter, MoveCallMetrics, MoveFunctionName,
@@ -67,6 +72,7 @@ pub const EVENT_SEQUENCE_NUMBER_STR: &str = "event_sequence_number";
 pub struct IndexerReader {
     pool: crate::PgConnectionPool,
     package_cache: PackageCache,
+    min_idle: Option<u32>,
 }
 
 // Impl for common initialization and utilities
@@ -89,6 +95,7 @@ impl IndexerReader {
 
         let pool = diesel::r2d2::Pool::builder()
             .max_size(config.pool_size)
+            .min_idle(config.min_idle)
             .connection_timeout(config.connection_timeout)
             .connection_customizer(Box::new(connection_config))
             .build(manager)
@@ -97,9 +104,21 @@ impl IndexerReader {
         Ok(Self {
             pool,
             package_cache: Default::default(),
+            min_idle: config.min_idle,
         })
     }
 
+    /// Number of idle connections the pool was configured to keep open, if any. When unset, the
+    /// pool keeps as many connections open as its size allows.
+    pub fn min_idle(&self) -> Option<u32> {
+        self.min_idle
+    }
+
+    /// Number of connections currently open in the pool, whether idle or in use.
+    pub fn open_connections(&self) -> u32 {
+        self.pool.state().connections
+    }
+
     fn get_connection(&self) -> Result<PgPoolConnection, IndexerError> {
         self.pool.get().map_err(|e| {
             IndexerError::PgPoolConnectionError(format!(
@@ -150,6 +169,185 @@ impl IndexerReader {
     {
         self.spawn_blocking(move |this| this.run_query(query)).await
     }
+
+    /// Like `run_query_async`, but if the returned future is dropped before the query completes
+    /// (e.g. because the client that issued the request went away), makes a best-effort attempt
+    /// to cancel the query on the server by calling `pg_cancel_backend` on the backend PID of the
+    /// connection running it, so that the connection is released back to the pool promptly.
+    ///
+    /// Limitations:
+    /// - Cancellation is issued from a separate connection, so it requires a spare connection in
+    ///   the pool; if none becomes available within the pool's connection timeout, the query runs
+    ///   to completion (or until it hits the statement timeout).
+    /// - It is inherently racy: the query may complete before the cancel request reaches the
+    ///   server, in which case the cancel is a no-op. The connection that ran the query is kept
+    ///   out of the pool until the cancel request is done, so it can never interrupt another
+    ///   query that reused the connection. The cancel can still hit an unrelated backend if the
+    ///   query's own connection is lost in the meantime and the server hands its PID to a new
+    ///   backend before the cancel arrives.
+    /// - Holding on to the connection means it is released only once the cancel request is done,
+    ///   which can take up to the pool's connection timeout if the pool has no spare connection.
+    /// - `pg_cancel_backend` only interrupts the statement currently executing on the backend.
+    ///   The blocking task itself keeps running until that statement returns, and the result of
+    ///   the query is discarded.
+    pub async fn run_cancellable_query<T, E, F>(&self, query: F) -> Result<T, IndexerError>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<T, E> + Send + 'static,
+        E: From<diesel::result::Error> + std::error::Error + Send + 'static,
+        T: Send + 'static,
+    {
+        let state = Arc::new(QueryCancellationState::default());
+        let guard = CancelQueryOnDrop {
+            reader: self.clone(),
+            state: state.clone(),
+            armed: true,
+        };
+
+        let result = self
+            .spawn_blocking(move |this| {
+                let mut connection = this.get_connection()?;
+                let backend_pid = diesel::select(sql::<Integer>("pg_backend_pid()"))
+                    .get_result::<i32>(&mut connection)
+                    .map_err(|e| IndexerError::PostgresReadError(e.to_string()))?;
+                state.backend_pid.store(backend_pid, Ordering::SeqCst);
+
+                // The caller may have gone away before we got hold of a connection, in which
+                // case the guard had no backend to cancel yet.
+                let result = if state.cancelled.load(Ordering::SeqCst) {
+                    Err(IndexerError::PostgresReadError(
+                        "Query cancelled before it started".to_string(),
+                    ))
+                } else {
+                    connection
+                        .build_transaction()
+                        .read_only()
+                        .run(query)
+                        .map_err(|e| IndexerError::PostgresReadError(e.to_string()))
+                };
+                // Only now may the connection go back to the pool.
+                state.release_backend();
+                result
+            })
+            .await;
+
+        guard.disarm();
+        result
+    }
+
+    /// Runs a trivial query on `count` connections, one after the other, so that idle connections
+    /// are exercised and connections that the server dropped are replaced before a real query
+    /// needs them. `count` is capped one below the pool size, so that there is always a connection
+    /// left for real queries.
+    pub async fn ping_connections(&self, count: u32) -> Result<(), IndexerError> {
+        let count = count.min(self.pool.max_size().saturating_sub(1));
+        self.spawn_blocking(move |this| {
+            // The pool hands out the connection that was returned last, so hold on to each pinged
+            // connection until all have been pinged, for each ping to reach a different one.
+            let mut connections = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let mut connection = this.get_connection()?;
+                diesel::select(sql::<Integer>("1"))
+                    .execute(&mut connection)
+                    .map_err(|e| IndexerError::PostgresReadError(e.to_string()))?;
+                connections.push(connection);
+            }
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Shared between `run_cancellable_query` and the blocking task running the query.
+#[derive(Default)]
+struct QueryCancellationState {
+    /// PID of the backend running the query, or 0 if no query is in flight or the guard has taken
+    /// the PID to cancel the query.
+    backend_pid: AtomicI32,
+    cancelled: AtomicBool,
+    /// Whether the cancel request for a PID taken by the guard is done, or was given up on.
+    cancel_finished: Mutex<bool>,
+    cancel_finished_signal: Condvar,
+}
+
+impl QueryCancellationState {
+    /// Called by the blocking task before its connection goes back to the pool. If the guard took
+    /// the backend PID to cancel the query, waits until the cancel request is done, so that it
+    /// cannot reach the backend once another query has checked out the same connection.
+    fn release_backend(&self) {
+        if self.backend_pid.swap(0, Ordering::SeqCst) != 0 {
+            // The guard never took the PID, and now it never will.
+            return;
+        }
+        let mut finished = self.cancel_finished.lock().unwrap();
+        while !*finished {
+            finished = self.cancel_finished_signal.wait(finished).unwrap();
+        }
+    }
+
+    fn finish_cancel(&self) {
+        *self.cancel_finished.lock().unwrap() = true;
+        self.cancel_finished_signal.notify_all();
+    }
+}
+
+/// Cancels the in-flight query tracked by `state` when dropped, unless it has been disarmed.
+struct CancelQueryOnDrop {
+    reader: IndexerReader,
+    state: Arc<QueryCancellationState>,
+    armed: bool,
+}
+
+impl CancelQueryOnDrop {
+    fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for CancelQueryOnDrop {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        self.state.cancelled.store(true, Ordering::SeqCst);
+        // Taking the PID tells the blocking task to hold on to its connection until the cancel
+        // request is done.
+        let backend_pid = self.state.backend_pid.swap(0, Ordering::SeqCst);
+        if backend_pid == 0 {
+            return;
+        }
+        // Lets the blocking task go however the cancel ends, including the task below being
+        // dropped without running.
+        let finished = CancelFinishedOnDrop(self.state.clone());
+
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let reader = self.reader.clone();
+        handle.spawn(async move {
+            let _finished = finished;
+            let cancelled = reader
+                .spawn_blocking(move |this| {
+                    let mut connection = this.get_connection()?;
+                    diesel::select(sql::<Bool>(&format!("pg_cancel_backend({backend_pid})")))
+                        .get_result::<bool>(&mut connection)
+                        .map_err(|e| IndexerError::PostgresReadError(e.to_string()))
+                })
+                .await;
+            if let Err(e) = cancelled {
+                tracing::warn!("Failed to cancel query on backend {backend_pid}: {e}");
+            }
+        });
+    }
+}
+
+/// Marks the cancel request of a `QueryCancellationState` as done when dropped.
+struct CancelFinishedOnDrop(Arc<QueryCancellationState>);
+
+impl Drop for CancelFinishedOnDrop {
+    fn drop(&mut self) {
+        self.0.finish_cancel();
+    }
 }
 
 thread_local! {
@@ -279,6 +279,7 @@ pub fn new_pg_connection_pool_impl(
     let pool_size = pool_size.unwrap_or(pool_config.pool_size);
     diesel::r2d2::Pool::builder()
         .max_size(pool_size)
+        .min_idle(pool_config.min_idle)
         .connection_timeout(pool_config.connection_timeout)
         .connection_customizer(Box::new(pool_config.connection_config()))
         .build(manager)
@@ -295,6 +296,9 @@ pub struct PgConnectionPoolConfig {
     pool_size: u32,
     connection_timeout: Duration,
     statement_timeout: Duration,
+    /// Number of idle connections the pool tries to maintain. `None` means as many as
+    /// `pool_size`.
+    min_idle: Option<u32>,
 }
 
 impl PgConnectionPoolConfig {
@@ -320,6 +324,10 @@ impl PgConnectionPoolConfig {
     pub fn set_statement_timeout(&mut self, timeout: Duration) {
         self.statement_timeout = timeout;
     }
+
+    pub fn set_min_idle(&mut self, min_idle: u32) {
+        self.min_idle = Some(min_idle);
+    }
 }
 
 impl Default for PgConnectionPoolConfig {
@@ -336,11 +344,15 @@ impl Default for PgConnectionPoolConfig {
             .ok()
             .and_then(|s| s.parse::<u64>().ok())
             .unwrap_or(Self::DEFAULT_STATEMENT_TIMEOUT);
+        let min_idle = std::env::var("DB_MIN_IDLE")
+            .ok()
+            .and_then(|s| s.parse::<u32>().ok());
 
         Self {
             pool_size: db_pool_size,
             connection_timeout: Duration::from_secs(conn_timeout_secs),
             statement_timeout: Duration::from_secs(statement_timeout_secs),
+            min_idle,
         }
     }
 }
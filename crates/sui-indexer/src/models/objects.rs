@@ -1,10 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{
-    collections::{BTreeMap, HashMap},
-    str::FromStr,
-};
+use std::{collections::BTreeMap, str::FromStr};
 
 use diesel::deserialize::FromSql;
 use diesel::pg::{Pg, PgValue};
@@ -14,9 +11,10 @@ use diesel::sql_types::{Bytea, Nullable, Record, VarChar};
 use diesel::SqlType;
 use diesel_derive_enum::DbEnum;
 use fastcrypto::encoding::{Base64, Encoding};
+use indexmap::map::Entry;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json;
-use std::collections::hash_map::Entry;
 
 use move_bytecode_utils::module_cache::GetModule;
 use sui_json_rpc_types::{SuiObjectData, SuiObjectRef, SuiRawData};
@@ -115,7 +113,7 @@ impl From<DeletedObject> for Object {
     }
 }
 
-#[derive(DbEnum, Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(DbEnum, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[ExistingTypePath = "crate::schema::sql_types::ObjectStatus"]
 #[serde(rename_all = "snake_case")]
 pub enum ObjectStatus {
@@ -502,12 +500,13 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
     bulk_insert_query
 }
 
+/// Keeps the highest version of each object, in the order in which objects first appear in
+/// `objects`, so that the output is the same from run to run.
 pub fn filter_latest_objects(objects: Vec<Object>) -> Vec<Object> {
     // Transactions in checkpoint are ordered by causal depedencies.
-    // But HashMap is not a lot more costly than HashSet, and it
-    // may be good to still keep the relative order of objects in
-    // the checkpoint.
-    let mut latest_objects = HashMap::new();
+    // But IndexMap is not a lot more costly than HashSet, and it
+    // keeps the relative order of objects in the checkpoint.
+    let mut latest_objects = IndexMap::new();
     for object in objects {
         match latest_objects.entry(object.object_id.clone()) {
             Entry::Vacant(e) => {
@@ -522,3 +521,167 @@ pub fn filter_latest_objects(objects: Vec<Object>) -> Vec<Object> {
     }
     latest_objects.into_values().collect()
 }
+
+/// The latest state of an object within a batch of changes: either the object itself, or a
+/// tombstone recording the version at which it was deleted.
+#[derive(Debug, Clone)]
+pub enum LatestObjectChange {
+    Live(Object),
+    Deleted(DeletedObject),
+}
+
+impl LatestObjectChange {
+    pub fn object_id(&self) -> &str {
+        match self {
+            LatestObjectChange::Live(o) => &o.object_id,
+            LatestObjectChange::Deleted(o) => &o.object_id,
+        }
+    }
+
+    pub fn version(&self) -> i64 {
+        match self {
+            LatestObjectChange::Live(o) => o.version,
+            LatestObjectChange::Deleted(o) => o.version,
+        }
+    }
+}
+
+/// Like `filter_latest_objects`, but also takes the deletions of the batch, so that an object
+/// whose latest change is a deletion is kept as a tombstone instead of being dropped or
+/// reported live at an earlier version. Objects are in order of first appearance, live objects
+/// before deletions.
+pub fn filter_latest_object_changes(
+    objects: Vec<Object>,
+    deletions: Vec<DeletedObject>,
+) -> Vec<LatestObjectChange> {
+    let mut latest_changes = IndexMap::new();
+    let changes = objects
+        .into_iter()
+        .map(LatestObjectChange::Live)
+        .chain(deletions.into_iter().map(LatestObjectChange::Deleted));
+    for change in changes {
+        match latest_changes.entry(change.object_id().to_string()) {
+            Entry::Vacant(e) => {
+                e.insert(change);
+            }
+            Entry::Occupied(mut e) => {
+                if change.version() > e.get().version() {
+                    e.insert(change);
+                }
+            }
+        }
+    }
+    latest_changes.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(object_id: &str, version: i64, object_status: ObjectStatus) -> Object {
+        Object {
+            epoch: 0,
+            checkpoint: 0,
+            object_id: object_id.to_string(),
+            version,
+            object_digest: String::new(),
+            owner_type: OwnerType::AddressOwner,
+            owner_address: None,
+            initial_shared_version: None,
+            previous_transaction: String::new(),
+            object_type: String::new(),
+            object_status,
+            has_public_transfer: true,
+            storage_rebate: 0,
+            bcs: vec![],
+        }
+    }
+
+    fn deleted(object_id: &str, version: i64) -> DeletedObject {
+        DeletedObject {
+            epoch: 0,
+            checkpoint: Some(0),
+            object_id: object_id.to_string(),
+            version,
+            object_digest: String::new(),
+            owner_type: OwnerType::AddressOwner,
+            previous_transaction: String::new(),
+            object_type: String::new(),
+            object_status: ObjectStatus::Deleted,
+            has_public_transfer: true,
+        }
+    }
+
+    #[test]
+    fn test_filter_latest_object_changes() {
+        let objects = vec![
+            // Created, mutated then deleted within the batch.
+            object("0x1", 1, ObjectStatus::Created),
+            object("0x1", 2, ObjectStatus::Mutated),
+            // Deleted, but its latest change is a later re-creation (unwrap).
+            object("0x2", 5, ObjectStatus::Unwrapped),
+            // Only mutated.
+            object("0x3", 3, ObjectStatus::Created),
+            object("0x3", 4, ObjectStatus::Mutated),
+        ];
+        let deletions = vec![deleted("0x1", 3), deleted("0x2", 4), deleted("0x4", 7)];
+
+        let mut latest: Vec<_> = filter_latest_object_changes(objects.clone(), deletions)
+            .into_iter()
+            .map(|change| match change {
+                LatestObjectChange::Live(o) => (o.object_id, o.version, true),
+                LatestObjectChange::Deleted(o) => (o.object_id, o.version, false),
+            })
+            .collect();
+        latest.sort();
+        assert_eq!(
+            latest,
+            vec![
+                ("0x1".to_string(), 3, false),
+                ("0x2".to_string(), 5, true),
+                ("0x3".to_string(), 4, true),
+                ("0x4".to_string(), 7, false),
+            ]
+        );
+
+        // Without deletions, only live objects are kept.
+        let mut live: Vec<_> = filter_latest_objects(objects)
+            .into_iter()
+            .map(|o| (o.object_id, o.version))
+            .collect();
+        live.sort();
+        assert_eq!(
+            live,
+            vec![
+                ("0x1".to_string(), 2),
+                ("0x2".to_string(), 5),
+                ("0x3".to_string(), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_latest_objects_keeps_first_seen_order() {
+        let objects = vec![
+            object("0x5", 1, ObjectStatus::Created),
+            object("0x3", 1, ObjectStatus::Created),
+            object("0x9", 2, ObjectStatus::Mutated),
+            object("0x5", 2, ObjectStatus::Mutated),
+            object("0x1", 1, ObjectStatus::Created),
+            object("0x3", 3, ObjectStatus::Mutated),
+        ];
+        let latest: Vec<_> = filter_latest_objects(objects)
+            .into_iter()
+            .map(|o| (o.object_id, o.version))
+            .collect();
+        assert_eq!(
+            latest,
+            vec![
+                ("0x5".to_string(), 2),
+                ("0x3".to_string(), 3),
+                ("0x9".to_string(), 2),
+                ("0x1".to_string(), 1),
+            ]
+        );
+    }
+}
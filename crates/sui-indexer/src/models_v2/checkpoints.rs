@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use diesel::prelude::*;
+use serde::Serialize;
 
 use sui_json_rpc_types::Checkpoint as RpcCheckpoint;
 use sui_types::base_types::TransactionDigest;
@@ -10,7 +11,7 @@ use sui_types::gas::GasCostSummary;
 
 use crate::errors::IndexerError;
 use crate::schema_v2::checkpoints;
-use crate::types_v2::IndexedCheckpoint;
+use crate::types_v2::{IndexedCheckpoint, IndexerResult};
 
 #[derive(Queryable, Insertable, Debug, Clone, Default)]
 #[diesel(table_name = checkpoints)]
@@ -33,9 +34,10 @@ pub struct StoredCheckpoint {
     pub end_of_epoch_data: Option<Vec<u8>>,
 }
 
-impl From<&IndexedCheckpoint> for StoredCheckpoint {
-    fn from(c: &IndexedCheckpoint) -> Self {
-        Self {
+impl TryFrom<&IndexedCheckpoint> for StoredCheckpoint {
+    type Error = IndexerError;
+    fn try_from(c: &IndexedCheckpoint) -> Result<Self, IndexerError> {
+        Ok(Self {
             sequence_number: c.sequence_number as i64,
             checkpoint_digest: c.checkpoint_digest.into_inner().to_vec(),
             epoch: c.epoch as i64,
@@ -55,17 +57,40 @@ impl From<&IndexedCheckpoint> for StoredCheckpoint {
             storage_cost: c.storage_cost as i64,
             storage_rebate: c.storage_rebate as i64,
             non_refundable_storage_fee: c.non_refundable_storage_fee as i64,
-            checkpoint_commitments: bcs::to_bytes(&c.checkpoint_commitments).unwrap(),
-            validator_signature: bcs::to_bytes(&c.validator_signature).unwrap(),
+            checkpoint_commitments: serialize_checkpoint_field(
+                c.sequence_number,
+                "checkpoint commitments",
+                &c.checkpoint_commitments,
+            )?,
+            validator_signature: serialize_checkpoint_field(
+                c.sequence_number,
+                "validator signature",
+                &c.validator_signature,
+            )?,
             end_of_epoch_data: c
                 .end_of_epoch_data
                 .as_ref()
-                .map(|d| bcs::to_bytes(d).unwrap()),
+                .map(|d| serialize_checkpoint_field(c.sequence_number, "end of epoch data", d))
+                .transpose()?,
             end_of_epoch: c.end_of_epoch_data.is_some(),
-        }
+        })
     }
 }
 
+/// BCS-serializes `field` of checkpoint `sequence_number`, reporting a failure as an error
+/// instead of panicking.
+fn serialize_checkpoint_field<T: Serialize + ?Sized>(
+    sequence_number: u64,
+    field: &str,
+    value: &T,
+) -> IndexerResult<Vec<u8>> {
+    bcs::to_bytes(value).map_err(|e| {
+        IndexerError::SerdeError(format!(
+            "Failed to serialize {field} of checkpoint {sequence_number} with err: {e:?}"
+        ))
+    })
+}
+
 impl TryFrom<StoredCheckpoint> for RpcCheckpoint {
     type Error = IndexerError;
     fn try_from(checkpoint: StoredCheckpoint) -> Result<RpcCheckpoint, IndexerError> {
@@ -153,3 +178,22 @@ impl TryFrom<StoredCheckpoint> for RpcCheckpoint {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_checkpoint_field_error() {
+        // BCS has no representation for floating point numbers.
+        let err = serialize_checkpoint_field(7, "validator signature", &1.5f64).unwrap_err();
+        assert!(matches!(
+            err,
+            IndexerError::SerdeError(msg) if msg.contains("validator signature of checkpoint 7")
+        ));
+        assert_eq!(
+            serialize_checkpoint_field(7, "validator signature", &1u64).unwrap(),
+            bcs::to_bytes(&1u64).unwrap()
+        );
+    }
+}
@@ -78,7 +78,7 @@ impl From<IndexedObject> for StoredObject {
                 .object
                 .type_()
                 .map(|t| t.to_canonical_string(/* with_prefix */ true)),
-            serialized_object: bcs::to_bytes(&o.object).unwrap(),
+            serialized_object: o.serialized_object,
             coin_type: o.coin_type,
             coin_balance: o.coin_balance.map(|b| b as i64),
             df_kind: o.df_info.as_ref().map(|k| match k.type_ {
@@ -313,7 +313,7 @@ mod tests {
     #[test]
     fn test_canonical_string_of_object_type_for_coin() {
         let test_obj = Object::new_gas_for_testing();
-        let indexed_obj = IndexedObject::from_object(1, test_obj, None);
+        let indexed_obj = IndexedObject::from_object(1, test_obj, None, None).unwrap();
 
         let stored_obj = StoredObject::from(indexed_obj);
 
@@ -330,7 +330,7 @@ mod tests {
     #[test]
     fn test_convert_stored_obj_to_sui_coin() {
         let test_obj = Object::new_gas_for_testing();
-        let indexed_obj = IndexedObject::from_object(1, test_obj, None);
+        let indexed_obj = IndexedObject::from_object(1, test_obj, None, None).unwrap();
 
         let stored_obj = StoredObject::from(indexed_obj);
 
@@ -380,7 +380,7 @@ mod tests {
             storage_rebate: 0,
         };
 
-        let indexed_obj = IndexedObject::from_object(1, object, None);
+        let indexed_obj = IndexedObject::from_object(1, object, None, None).unwrap();
 
         let stored_obj = StoredObject::from(indexed_obj);
 
@@ -10,6 +10,8 @@ use diesel::prelude::*;
 #[diesel(table_name = packages, primary_key(package_id))]
 pub struct StoredPackage {
     pub package_id: Vec<u8>,
+    pub original_id: Vec<u8>,
+    pub package_version: i64,
     pub move_package: Vec<u8>,
 }
 
@@ -17,6 +19,8 @@ impl From<IndexedPackage> for StoredPackage {
     fn from(p: IndexedPackage) -> Self {
         Self {
             package_id: p.package_id.to_vec(),
+            original_id: p.move_package.original_package_id().to_vec(),
+            package_version: p.move_package.version().value() as i64,
             move_package: bcs::to_bytes(&p.move_package).unwrap(),
         }
     }
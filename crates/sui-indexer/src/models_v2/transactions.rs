@@ -91,7 +91,7 @@ impl From<&IndexedTransaction> for StoredTransaction {
                 .map(|e| Some(bcs::to_bytes(&e).unwrap()))
                 .collect(),
             timestamp_ms: tx.timestamp_ms as i64,
-            transaction_kind: tx.transaction_kind.clone() as i16,
+            transaction_kind: tx.transaction_kind.as_i16(),
             success_command_count: tx.effects.status().is_ok() as i16 * cmd_count as i16,
         }
     }
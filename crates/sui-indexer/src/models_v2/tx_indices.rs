@@ -103,11 +103,11 @@ impl TxIndex {
         let tx_calls = self
             .move_calls
             .iter()
-            .map(|(p, m, f)| StoredTxCalls {
+            .map(|call| StoredTxCalls {
                 tx_sequence_number,
-                package: p.to_vec(),
-                module: m.to_string(),
-                func: f.to_string(),
+                package: call.package.to_vec(),
+                module: call.module.clone(),
+                func: call.function.clone(),
             })
             .collect();
         (
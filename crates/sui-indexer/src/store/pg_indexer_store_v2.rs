@@ -265,8 +265,8 @@ impl PgIndexerStoreV2 {
 
         let checkpoints = checkpoints
             .iter()
-            .map(StoredCheckpoint::from)
-            .collect::<Vec<_>>();
+            .map(StoredCheckpoint::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
         transactional_blocking_with_retry!(
             &self.blocking_cp,
             |conn| {
@@ -386,7 +386,10 @@ impl PgIndexerStoreV2 {
                         // TODO: race condition is possible here. Figure out how to avoid/detect
                         .on_conflict(packages::package_id)
                         .do_update()
-                        .set(packages::move_package.eq(excluded(packages::move_package)))
+                        .set((
+                            packages::package_version.eq(excluded(packages::package_version)),
+                            packages::move_package.eq(excluded(packages::move_package)),
+                        ))
                         .execute(conn)
                         .map_err(IndexerError::from)
                         .context("Failed to write packages to PostgresDB")?;
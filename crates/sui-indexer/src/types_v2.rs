@@ -2,27 +2,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors::IndexerError;
-use move_core_types::language_storage::StructTag;
+use crate::models::objects::ObjectStatus;
+use itertools::Itertools;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use sui_json_rpc_types::ObjectChange;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use sui_json_rpc_types::{ObjectChange, SuiTransactionBlockResponse};
 use sui_types::base_types::{ObjectDigest, SequenceNumber};
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::coin::Coin;
 use sui_types::crypto::AggregateAuthoritySignature;
 use sui_types::digests::TransactionDigest;
-use sui_types::dynamic_field::DynamicFieldInfo;
-use sui_types::effects::TransactionEffects;
-use sui_types::event::SystemEpochInfoEvent;
+use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName, DynamicFieldType};
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::event::{Event, SystemEpochInfoEvent};
+use sui_types::gas::GasCostSummary;
+use sui_types::gas_coin::GAS;
 use sui_types::messages_checkpoint::{
     CertifiedCheckpointSummary, CheckpointCommitment, CheckpointDigest, EndOfEpochData,
 };
 use sui_types::move_package::MovePackage;
-use sui_types::object::{Object, Owner};
+use sui_types::object::{Data, Object, Owner};
 use sui_types::sui_serde::SuiStructTag;
 use sui_types::sui_system_state::sui_system_state_summary::{
     SuiSystemStateSummary, SuiValidatorSummary,
 };
-use sui_types::transaction::SenderSignedData;
+use sui_types::transaction::{Command, SenderSignedData, TransactionDataAPI};
+use tracing::warn;
 
 pub type IndexerResult<T> = Result<T, IndexerError>;
 
@@ -47,15 +54,31 @@ pub struct IndexedCheckpoint {
     pub end_of_epoch: bool,
 }
 
+/// Computation plus storage cost, minus the storage rebate, saturated to fit the `i64` column.
+/// The sum is taken in `i128` so that it can't overflow on the way.
+fn total_gas_cost(sequence_number: u64, gas_cost_summary: &GasCostSummary) -> i64 {
+    let total = gas_cost_summary.computation_cost as i128 + gas_cost_summary.storage_cost as i128
+        - gas_cost_summary.storage_rebate as i128;
+    let saturated = total.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+    if saturated as i128 != total {
+        warn!(
+            "Total gas cost {total} of checkpoint {sequence_number} does not fit in an i64, \
+            saturating to {saturated}"
+        );
+    }
+    saturated
+}
+
 impl IndexedCheckpoint {
     pub fn from_sui_checkpoint(
         checkpoint: &sui_types::messages_checkpoint::CertifiedCheckpointSummary,
         contents: &sui_types::messages_checkpoint::CheckpointContents,
         successful_tx_num: usize,
     ) -> Self {
-        let total_gas_cost = checkpoint.epoch_rolling_gas_cost_summary.computation_cost as i64
-            + checkpoint.epoch_rolling_gas_cost_summary.storage_cost as i64
-            - checkpoint.epoch_rolling_gas_cost_summary.storage_rebate as i64;
+        let total_gas_cost = total_gas_cost(
+            checkpoint.sequence_number,
+            &checkpoint.epoch_rolling_gas_cost_summary,
+        );
         let tx_digests = contents.iter().map(|t| t.transaction).collect::<Vec<_>>();
         let auth_sig = &checkpoint.auth_sig().signature;
         Self {
@@ -80,6 +103,27 @@ impl IndexedCheckpoint {
             checkpoint_commitments: checkpoint.checkpoint_commitments.clone(),
         }
     }
+
+    pub fn summary(&self) -> CheckpointSummary {
+        CheckpointSummary {
+            sequence_number: self.sequence_number,
+            checkpoint_digest: self.checkpoint_digest,
+            epoch: self.epoch,
+            timestamp_ms: self.timestamp_ms,
+            tx_count: self.tx_digests.len(),
+        }
+    }
+}
+
+/// Lightweight projection of an `IndexedCheckpoint` for endpoints that list checkpoints and
+/// don't need its digests, commitments or signature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CheckpointSummary {
+    pub sequence_number: u64,
+    pub checkpoint_digest: CheckpointDigest,
+    pub epoch: u64,
+    pub timestamp_ms: u64,
+    pub tx_count: usize,
 }
 
 #[derive(Debug, Default)]
@@ -163,6 +207,30 @@ impl IndexedEpochInfo {
     }
 }
 
+/// Merges the epoch row written at epoch start with the end-of-epoch row built
+/// by `IndexedEpochInfo::from_end_of_epoch_data`. Start fields are kept from
+/// `start`, and every end-of-epoch field is taken from `end`.
+pub fn finalize_epoch(start: IndexedEpochInfo, end: &IndexedEpochInfo) -> IndexedEpochInfo {
+    IndexedEpochInfo {
+        epoch_total_transactions: end.epoch_total_transactions,
+        last_checkpoint_id: end.last_checkpoint_id,
+        epoch_end_timestamp: end.epoch_end_timestamp,
+        storage_fund_reinvestment: end.storage_fund_reinvestment,
+        storage_charge: end.storage_charge,
+        storage_rebate: end.storage_rebate,
+        storage_fund_balance: end.storage_fund_balance,
+        stake_subsidy_amount: end.stake_subsidy_amount,
+        total_gas_fees: end.total_gas_fees,
+        total_stake_rewards_distributed: end.total_stake_rewards_distributed,
+        leftover_storage_fund_inflow: end.leftover_storage_fund_inflow,
+        new_total_stake: end.new_total_stake,
+        epoch_commitments: end.epoch_commitments.clone(),
+        next_epoch_reference_gas_price: end.next_epoch_reference_gas_price,
+        next_epoch_protocol_version: end.next_epoch_protocol_version,
+        ..start
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IndexedEvent {
     pub tx_sequence_number: u64,
@@ -201,6 +269,9 @@ impl IndexedEvent {
     }
 }
 
+/// Kind of owner, persisted as its `i16` discriminant. Existing discriminants must never change;
+/// owner kinds added to `Owner` get a new variant appended here, together with its arm in
+/// `owner_to_owner_info`, `owner_from_info` and `TryFrom<i16>`.
 #[derive(Debug, Copy, Clone)]
 pub enum OwnerType {
     Immutable = 0,
@@ -237,6 +308,42 @@ pub fn owner_to_owner_info(owner: &Owner) -> (OwnerType, Option<SuiAddress>) {
     }
 }
 
+/// Inverse of `owner_to_owner_info`. Shared owners also need the version at which the object
+/// became shared, which the owner columns don't carry.
+pub fn owner_from_info(
+    owner_type: OwnerType,
+    owner_id: Option<SuiAddress>,
+    initial_shared_version: Option<u64>,
+) -> IndexerResult<Owner> {
+    Ok(match (owner_type, owner_id, initial_shared_version) {
+        (OwnerType::Address, Some(address), _) => Owner::AddressOwner(address),
+        (OwnerType::Object, Some(address), _) => Owner::ObjectOwner(address),
+        (OwnerType::Shared, _, Some(version)) => Owner::Shared {
+            initial_shared_version: SequenceNumber::from_u64(version),
+        },
+        (OwnerType::Immutable, _, _) => Owner::Immutable,
+        (owner_type, owner_id, initial_shared_version) => {
+            return Err(IndexerError::PersistentStorageDataCorruptionError(format!(
+                "{owner_type:?} owner with owner id {owner_id:?} and initial shared version {initial_shared_version:?}"
+            )))
+        }
+    })
+}
+
+/// Canonical string form of `owner`, which unlike `owner_to_owner_info` keeps shared and
+/// immutable owners apart: `address:0x..`, `object:0x..`, `shared:<initial_version>` or
+/// `immutable`.
+pub fn owner_to_canonical_string(owner: &Owner) -> String {
+    match owner {
+        Owner::AddressOwner(address) => format!("address:{address}"),
+        Owner::ObjectOwner(address) => format!("object:{address}"),
+        Owner::Shared {
+            initial_shared_version,
+        } => format!("shared:{}", initial_shared_version.value()),
+        Owner::Immutable => "immutable".to_string(),
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum DynamicFieldKind {
     DynamicField = 0,
@@ -247,45 +354,182 @@ pub enum DynamicFieldKind {
 pub struct IndexedObject {
     pub object_id: ObjectID,
     pub object_version: u64,
+    /// The version this object had before the transaction that wrote `object_version`, or `None`
+    /// if that transaction created or unwrapped it.
+    pub previous_version: Option<u64>,
     pub object_digest: ObjectDigest,
     pub checkpoint_sequence_number: u64,
     pub owner_type: OwnerType,
     pub owner_id: Option<SuiAddress>,
+    /// The version at which the object became shared, for shared objects only.
+    pub owner_initial_shared_version: Option<u64>,
+    /// The object that owns this one, for objects owned by another object such as dynamic field
+    /// children. This is the same as `owner_id`, read as an object id.
+    pub parent_object_id: Option<ObjectID>,
     pub object: Object,
+    /// BCS bytes of `object`, serialized once so that writers and exporters can reuse them.
+    pub serialized_object: Vec<u8>,
     pub coin_type: Option<String>,
     pub coin_balance: Option<u64>,
     pub df_info: Option<DynamicFieldInfo>,
+    /// The dynamic field's name rendered as a string, so that fields can be looked up by name
+    /// without decoding `df_info`. `None` for non-dynamic-field objects and for names that could
+    /// not be rendered.
+    pub df_name: Option<String>,
 }
 
 impl IndexedObject {
     pub fn from_object(
         checkpoint_sequence_number: u64,
         object: Object,
+        previous_version: Option<u64>,
         df_info: Option<DynamicFieldInfo>,
-    ) -> Self {
+    ) -> IndexerResult<Self> {
+        let serialized_object = bcs::to_bytes(&object).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to serialize object {} with err: {e:?}",
+                object.id()
+            ))
+        })?;
         let (owner_type, owner_id) = owner_to_owner_info(&object.owner);
+        let owner_initial_shared_version = match object.owner {
+            Owner::Shared {
+                initial_shared_version,
+            } => Some(initial_shared_version.value()),
+            _ => None,
+        };
+        let parent_object_id = match object.owner {
+            Owner::ObjectOwner(parent) => Some(ObjectID::from(parent)),
+            _ => None,
+        };
         let coin_type = object
             .coin_type_maybe()
             .map(|t| t.to_canonical_string(/* with_prefix */ true));
-        let coin_balance = if coin_type.is_some() {
-            Some(object.get_coin_value_unsafe())
-        } else {
-            None
+        // Read the balance defensively: a malformed coin must not take the indexer down.
+        let coin_balance = match Coin::extract_balance_if_coin(&object) {
+            Ok(balance) => balance,
+            Err(e) => {
+                warn!(
+                    "Failed to extract the balance of coin object {} with err: {e:?}",
+                    object.id()
+                );
+                None
+            }
         };
+        let df_name = df_info
+            .as_ref()
+            .and_then(|info| df_name_to_string(&info.name));
 
-        Self {
+        Ok(Self {
             checkpoint_sequence_number,
             object_id: object.id(),
             object_version: object.version().value(),
+            previous_version,
             object_digest: object.digest(),
             owner_type,
             owner_id,
+            owner_initial_shared_version,
+            parent_object_id,
             object,
+            serialized_object,
             coin_type,
             coin_balance,
             df_info,
+            df_name,
+        })
+    }
+
+    /// Classifies the object from its type and dynamic field info, so that consumers don't have to
+    /// match on type strings.
+    pub fn classify(&self) -> ObjectClass {
+        match &self.object.data {
+            Data::Package(_) => ObjectClass::Package,
+            Data::Move(object) if object.is_coin() => ObjectClass::Coin,
+            Data::Move(object) if object.is_staked_sui() => ObjectClass::StakedSui,
+            Data::Move(_) if self.df_info.is_some() => ObjectClass::DynamicField,
+            Data::Move(_) => ObjectClass::Other,
         }
     }
+
+    /// `coin_balance` widened to `u128`, so that balances can be summed without overflowing.
+    pub fn coin_balance_u128(&self) -> Option<u128> {
+        self.coin_balance.map(u128::from)
+    }
+}
+
+/// What kind of object an `IndexedObject` is, as far as indexing is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectClass {
+    Coin,
+    StakedSui,
+    Package,
+    DynamicField,
+    Other,
+}
+
+/// A dynamic field, indexed under its parent so that a parent's fields can be listed and filtered
+/// by name type without decoding `DynamicFieldInfo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedDynamicField {
+    pub parent_object_id: ObjectID,
+    /// The `Field` object that stores the dynamic field.
+    pub field_object_id: ObjectID,
+    pub field_object_version: u64,
+    pub kind: DynamicFieldType,
+    /// The name's type as a canonical type string.
+    pub name_type: String,
+    pub name_bcs: Vec<u8>,
+    /// The type of the field's value, or of the object it points to for dynamic object fields.
+    pub value_type: String,
+    /// The object a dynamic object field points to. `None` for plain dynamic fields.
+    pub value_object_id: Option<ObjectID>,
+}
+
+impl IndexedDynamicField {
+    pub fn from_object(parent: ObjectID, object: &Object, df_info: &DynamicFieldInfo) -> Self {
+        let value_object_id = match df_info.type_ {
+            DynamicFieldType::DynamicField => None,
+            DynamicFieldType::DynamicObject => Some(df_info.object_id),
+        };
+        Self {
+            parent_object_id: parent,
+            field_object_id: object.id(),
+            field_object_version: object.version().value(),
+            kind: df_info.type_.clone(),
+            name_type: df_info
+                .name
+                .type_
+                .to_canonical_string(/* with_prefix */ true),
+            name_bcs: df_info.bcs_name.clone(),
+            value_type: df_info.object_type.clone(),
+            value_object_id,
+        }
+    }
+}
+
+/// Renders a dynamic field name for indexing. String keys are stored as-is rather than as quoted
+/// JSON, and a name whose value could not be decoded (`null`) is not rendered at all.
+fn df_name_to_string(name: &DynamicFieldName) -> Option<String> {
+    match &name.value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(value) => Some(value.clone()),
+        value => Some(value.to_string()),
+    }
+}
+
+/// Splits a batch of objects into `(coins, others)` based on `coin_type`,
+/// preserving the relative order of objects within each batch.
+pub fn partition_coins(objects: Vec<IndexedObject>) -> (Vec<IndexedObject>, Vec<IndexedObject>) {
+    objects.into_iter().partition(|o| o.coin_type.is_some())
+}
+
+/// Sums the balances of the coins among `objects`, ignoring non-coin objects. The total is kept
+/// as a `u128`, since the sum of many `u64` balances can exceed `u64::MAX`.
+pub fn total_coin_balance<'a>(objects: impl IntoIterator<Item = &'a IndexedObject>) -> u128 {
+    objects
+        .into_iter()
+        .filter_map(IndexedObject::coin_balance_u128)
+        .sum()
 }
 
 #[derive(Debug)]
@@ -293,12 +537,79 @@ pub struct IndexedPackage {
     pub package_id: ObjectID,
     pub move_package: MovePackage,
     pub checkpoint_sequence_number: u64,
+    // Denormalized from `move_package`, so consumers don't have to go through the whole package.
+    pub version: u64,
+    pub modules: Vec<String>,
+    /// The package's linkage table: for each original dependency package id, the id and version
+    /// of the package it links against.
+    pub linkage: BTreeMap<ObjectID, (ObjectID, SequenceNumber)>,
 }
 
-#[derive(Debug, Clone)]
+impl IndexedPackage {
+    pub fn from_package(checkpoint_sequence_number: u64, move_package: MovePackage) -> Self {
+        let modules = move_package
+            .serialized_module_map()
+            .keys()
+            .cloned()
+            .collect();
+        let linkage = move_package
+            .linkage_table()
+            .iter()
+            .map(|(original_id, info)| (*original_id, (info.upgraded_id, info.upgraded_version)))
+            .collect();
+        Self {
+            package_id: move_package.id(),
+            version: move_package.version().value(),
+            modules,
+            linkage,
+            move_package,
+            checkpoint_sequence_number,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransactionKind {
-    SystemTransaction = 0,
-    ProgrammableTransaction = 1,
+    SystemTransaction(SystemTransactionKind),
+    ProgrammableTransaction,
+}
+
+/// Which kind of system transaction a `TransactionKind::SystemTransaction` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemTransactionKind {
+    ChangeEpoch,
+    Genesis,
+    ConsensusCommitPrologue,
+    AuthenticatorStateUpdate,
+    RandomnessStateUpdate,
+    EndOfEpochTransaction,
+}
+
+impl TransactionKind {
+    /// The value persisted in the `transaction_kind` column, which only distinguishes system
+    /// transactions from programmable ones.
+    pub fn as_i16(&self) -> i16 {
+        match self {
+            Self::SystemTransaction(_) => 0,
+            Self::ProgrammableTransaction => 1,
+        }
+    }
+}
+
+impl From<&sui_types::transaction::TransactionKind> for TransactionKind {
+    fn from(kind: &sui_types::transaction::TransactionKind) -> Self {
+        use sui_types::transaction::TransactionKind as Kind;
+        let system_kind = match kind {
+            Kind::ProgrammableTransaction(_) => return Self::ProgrammableTransaction,
+            Kind::ChangeEpoch(_) => SystemTransactionKind::ChangeEpoch,
+            Kind::Genesis(_) => SystemTransactionKind::Genesis,
+            Kind::ConsensusCommitPrologue(_) => SystemTransactionKind::ConsensusCommitPrologue,
+            Kind::AuthenticatorStateUpdate(_) => SystemTransactionKind::AuthenticatorStateUpdate,
+            Kind::RandomnessStateUpdate(_) => SystemTransactionKind::RandomnessStateUpdate,
+            Kind::EndOfEpochTransaction(_) => SystemTransactionKind::EndOfEpochTransaction,
+        };
+        Self::SystemTransaction(system_kind)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -314,9 +625,281 @@ pub struct IndexedTransaction {
     pub events: Vec<sui_types::event::Event>,
     pub transaction_kind: TransactionKind,
     pub successful_tx_num: u64,
+    // Denormalized from `effects.gas_cost_summary()`
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub non_refundable_storage_fee: u64,
 }
 
-#[derive(Debug, Clone)]
+impl IndexedTransaction {
+    /// Lazily flattens the transaction's object changes into DB rows, so that the persistence
+    /// layer can stream them out instead of materializing every row up front.
+    pub fn object_change_rows(&self) -> impl Iterator<Item = IndexerResult<ObjectChangeRow>> + '_ {
+        self.object_changes
+            .iter()
+            .map(|change| change.to_db_row(self.tx_sequence_number))
+    }
+
+    /// Net change in the SUI balance of `address` over this transaction, including gas. Zero if
+    /// the transaction did not touch any SUI owned by `address`.
+    pub fn net_sui_for(&self, address: &SuiAddress) -> i128 {
+        let sui = GAS::type_tag();
+        self.balance_change
+            .iter()
+            .filter(|change| change.coin_type == sui)
+            .filter(|change| address_owner(&change.owner).as_ref() == Some(address))
+            .map(|change| change.amount)
+            .sum()
+    }
+
+    /// Recomputes the balance changes of this transaction from its coin object changes, to check
+    /// `balance_change` against. Object changes don't carry coin values, so this needs the input
+    /// objects (as they were before the transaction) and output objects of the transaction.
+    /// Changes are netted per owner and coin type, dropping those that net to zero, and ordered
+    /// by owner then coin type.
+    pub fn balance_changes_from_object_changes(
+        &self,
+        input_objects: &[Object],
+        output_objects: &[Object],
+    ) -> Vec<sui_json_rpc_types::BalanceChange> {
+        let coin_balance = |objects: &[Object], object_id: &ObjectID| {
+            let object = objects.iter().find(|object| object.id() == *object_id)?;
+            let coin_type = object.coin_type_maybe()?;
+            let balance = Coin::extract_balance_if_coin(object).ok()??;
+            Some((object.owner, coin_type, balance as i128))
+        };
+
+        let mut balances: BTreeMap<(Owner, TypeTag), i128> = BTreeMap::new();
+        for change in &self.object_changes {
+            let (spent, received) = match change {
+                IndexedObjectChange::Created { object_id, .. } => (None, Some(object_id)),
+                IndexedObjectChange::Mutated { object_id, .. }
+                | IndexedObjectChange::Transferred { object_id, .. } => {
+                    (Some(object_id), Some(object_id))
+                }
+                IndexedObjectChange::Deleted { object_id, .. }
+                | IndexedObjectChange::Wrapped { object_id, .. } => (Some(object_id), None),
+                IndexedObjectChange::Published { .. } => (None, None),
+            };
+            if let Some((owner, coin_type, value)) =
+                spent.and_then(|object_id| coin_balance(input_objects, object_id))
+            {
+                *balances.entry((owner, coin_type)).or_default() -= value;
+            }
+            if let Some((owner, coin_type, value)) =
+                received.and_then(|object_id| coin_balance(output_objects, object_id))
+            {
+                *balances.entry((owner, coin_type)).or_default() += value;
+            }
+        }
+
+        balances
+            .into_iter()
+            .filter(|(_, amount)| *amount != 0)
+            .map(
+                |((owner, coin_type), amount)| sui_json_rpc_types::BalanceChange {
+                    owner,
+                    coin_type,
+                    amount,
+                },
+            )
+            .collect()
+    }
+
+    /// Whether this is a plain coin transfer: a programmable transaction that only splits, merges
+    /// and transfers objects, with at least one transfer. Such transactions call no Move code and
+    /// can be indexed on the fast path.
+    pub fn is_simple_transfer(&self) -> bool {
+        let sui_types::transaction::TransactionKind::ProgrammableTransaction(pt) =
+            self.sender_signed_data.intent_message().value.kind()
+        else {
+            return false;
+        };
+        let only_coin_commands = pt.commands.iter().all(|command| {
+            matches!(
+                command,
+                Command::TransferObjects(..) | Command::SplitCoins(..) | Command::MergeCoins(..)
+            )
+        });
+        only_coin_commands
+            && pt
+                .commands
+                .iter()
+                .any(|command| matches!(command, Command::TransferObjects(..)))
+    }
+
+    /// The packages this transaction interacted with: the targets of its Move calls and the
+    /// packages it published or upgraded to.
+    pub fn touched_packages(&self) -> BTreeSet<ObjectID> {
+        let called = extract_move_calls(&self.sender_signed_data)
+            .into_iter()
+            .map(|call| call.package);
+        let published = self
+            .object_changes
+            .iter()
+            .filter_map(|change| match change {
+                IndexedObjectChange::Published { package_id, .. } => Some(*package_id),
+                _ => None,
+            });
+        called.chain(published).collect()
+    }
+
+    /// The `TxIndex` row for this transaction, derived from its transaction data and effects.
+    pub fn to_tx_index(&self) -> TxIndex {
+        let tx = self.sender_signed_data.transaction_data();
+        let input_objects = tx
+            .input_objects()
+            .expect("committed txns have been validated")
+            .into_iter()
+            .map(|obj_kind| obj_kind.object_id())
+            .collect();
+        let changed_objects = self.effects.all_changed_objects();
+        let recipients = changed_objects
+            .iter()
+            .filter_map(|(_object_ref, owner, _write_kind)| match owner {
+                Owner::AddressOwner(address) => Some(*address),
+                _ => None,
+            })
+            .unique()
+            .collect();
+        TxIndex {
+            tx_sequence_number: self.tx_sequence_number,
+            transaction_digest: self.tx_digest,
+            checkpoint_sequence_number: self.checkpoint_sequence_number,
+            input_objects,
+            changed_objects: changed_objects
+                .into_iter()
+                .map(|(object_ref, _owner, _write_kind)| object_ref.0)
+                .collect(),
+            payers: vec![tx.gas_owner()],
+            senders: vec![tx.sender()],
+            recipients,
+            move_calls: extract_move_calls(&self.sender_signed_data),
+        }
+    }
+}
+
+/// The object changes, balance changes and events of a transaction, rebuilt from an RPC response
+/// rather than from checkpoint data, e.g. when backfilling from archived responses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedTransactionChanges {
+    pub object_changes: Vec<IndexedObjectChange>,
+    pub balance_change: Vec<sui_json_rpc_types::BalanceChange>,
+    pub events: Vec<Event>,
+}
+
+impl TryFrom<&SuiTransactionBlockResponse> for IndexedTransactionChanges {
+    type Error = IndexerError;
+
+    /// Fails if the response was fetched without object changes, balance changes or events.
+    fn try_from(response: &SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+        let missing = |field: &str| {
+            IndexerError::DataTransformationError(format!(
+                "Response for transaction {} has no {field}, it must be fetched with them shown",
+                response.digest
+            ))
+        };
+        let object_changes = response
+            .object_changes
+            .as_ref()
+            .ok_or_else(|| missing("object changes"))?
+            .iter()
+            .cloned()
+            .map(IndexedObjectChange::from)
+            .collect();
+        let balance_change = response
+            .balance_changes
+            .clone()
+            .ok_or_else(|| missing("balance changes"))?;
+        let events = response
+            .events
+            .as_ref()
+            .ok_or_else(|| missing("events"))?
+            .data
+            .iter()
+            .map(|event| Event {
+                package_id: event.package_id,
+                transaction_module: event.transaction_module.clone(),
+                sender: event.sender,
+                type_: event.type_.clone(),
+                contents: event.bcs.clone(),
+            })
+            .collect();
+        Ok(Self {
+            object_changes,
+            balance_change,
+            events,
+        })
+    }
+}
+
+/// Assembles the `IndexedTransaction`s of a checkpoint from its transactions and, for each of
+/// them in the same order, its effects, events and `(balance changes, object changes)`. The
+/// transactions are numbered consecutively from `first_tx_sequence_number`.
+pub fn index_transactions(
+    checkpoint_sequence_number: u64,
+    timestamp_ms: u64,
+    first_tx_sequence_number: u64,
+    transactions: Vec<SenderSignedData>,
+    effects: Vec<TransactionEffects>,
+    events: Vec<Vec<Event>>,
+    changes: Vec<(
+        Vec<sui_json_rpc_types::BalanceChange>,
+        Vec<IndexedObjectChange>,
+    )>,
+) -> IndexerResult<Vec<IndexedTransaction>> {
+    let count = transactions.len();
+    if effects.len() != count || events.len() != count || changes.len() != count {
+        return Err(IndexerError::InvalidArgumentError(format!(
+            "Checkpoint {checkpoint_sequence_number} has {count} transactions but {} effects, {} \
+            event lists and {} object change lists",
+            effects.len(),
+            events.len(),
+            changes.len(),
+        )));
+    }
+
+    Ok(transactions
+        .into_iter()
+        .zip(effects)
+        .zip(events)
+        .zip(changes)
+        .zip(first_tx_sequence_number..)
+        .map(
+            |((((sender_signed_data, effects), events), changes), tx_sequence_number)| {
+                let (balance_change, object_changes) = changes;
+                let tx = sender_signed_data.transaction_data();
+                let transaction_kind = TransactionKind::from(tx.kind());
+                let successful_tx_num = if effects.status().is_ok() {
+                    tx.kind().tx_count() as u64
+                } else {
+                    0
+                };
+                let gas_cost_summary = effects.gas_cost_summary().clone();
+                IndexedTransaction {
+                    tx_sequence_number,
+                    tx_digest: *effects.transaction_digest(),
+                    sender_signed_data,
+                    effects,
+                    checkpoint_sequence_number,
+                    timestamp_ms,
+                    object_changes,
+                    balance_change,
+                    events,
+                    transaction_kind,
+                    successful_tx_num,
+                    computation_cost: gas_cost_summary.computation_cost,
+                    storage_cost: gas_cost_summary.storage_cost,
+                    storage_rebate: gas_cost_summary.storage_rebate,
+                    non_refundable_storage_fee: gas_cost_summary.non_refundable_storage_fee,
+                }
+            },
+        )
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TxIndex {
     pub tx_sequence_number: u64,
     pub transaction_digest: TransactionDigest,
@@ -326,7 +909,43 @@ pub struct TxIndex {
     pub payers: Vec<SuiAddress>,
     pub senders: Vec<SuiAddress>,
     pub recipients: Vec<SuiAddress>,
-    pub move_calls: Vec<(ObjectID, String, String)>,
+    pub move_calls: Vec<TxMoveCall>,
+}
+
+/// A Move call made by a transaction, as indexed in `TxIndex::move_calls`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxMoveCall {
+    pub package: ObjectID,
+    pub module: String,
+    pub function: String,
+    /// The call's type arguments as canonical type strings, in order.
+    pub type_arguments: Vec<String>,
+}
+
+/// Every Move call in the transaction, in command order, as stored in `TxIndex::move_calls`.
+/// Empty for system transactions.
+pub fn extract_move_calls(data: &SenderSignedData) -> Vec<TxMoveCall> {
+    let sui_types::transaction::TransactionKind::ProgrammableTransaction(pt) =
+        data.intent_message().value.kind()
+    else {
+        return vec![];
+    };
+    pt.commands
+        .iter()
+        .filter_map(|command| match command {
+            Command::MoveCall(call) => Some(TxMoveCall {
+                package: call.package,
+                module: call.module.to_string(),
+                function: call.function.to_string(),
+                type_arguments: call
+                    .type_arguments
+                    .iter()
+                    .map(|t| t.to_canonical_string(/* with_prefix */ true))
+                    .collect(),
+            }),
+            _ => None,
+        })
+        .collect()
 }
 
 // ObjectChange is not bcs deserializable, IndexedObjectChange is.
@@ -387,6 +1006,55 @@ pub enum IndexedObjectChange {
     },
 }
 
+/// A single object change, flattened into the shape it is persisted in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectChangeRow {
+    pub tx_sequence_number: u64,
+    pub object_id: ObjectID,
+    pub version: SequenceNumber,
+    /// bcs serialized `IndexedObjectChange`
+    pub object_change: Vec<u8>,
+}
+
+impl IndexedObjectChange {
+    pub fn object_id(&self) -> ObjectID {
+        match self {
+            Self::Published { package_id, .. } => *package_id,
+            Self::Transferred { object_id, .. }
+            | Self::Mutated { object_id, .. }
+            | Self::Deleted { object_id, .. }
+            | Self::Wrapped { object_id, .. }
+            | Self::Created { object_id, .. } => *object_id,
+        }
+    }
+
+    pub fn version(&self) -> SequenceNumber {
+        match self {
+            Self::Published { version, .. }
+            | Self::Transferred { version, .. }
+            | Self::Mutated { version, .. }
+            | Self::Deleted { version, .. }
+            | Self::Wrapped { version, .. }
+            | Self::Created { version, .. } => *version,
+        }
+    }
+
+    pub fn to_db_row(&self, tx_sequence_number: u64) -> IndexerResult<ObjectChangeRow> {
+        let object_change = bcs::to_bytes(self).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to serialize object change of {} with err: {e:?}",
+                self.object_id()
+            ))
+        })?;
+        Ok(ObjectChangeRow {
+            tx_sequence_number,
+            object_id: self.object_id(),
+            version: self.version(),
+            object_change,
+        })
+    }
+}
+
 impl From<ObjectChange> for IndexedObjectChange {
     fn from(oc: ObjectChange) -> Self {
         match oc {
@@ -560,3 +1228,1504 @@ impl From<IndexedObjectChange> for ObjectChange {
         }
     }
 }
+
+/// Converts `changes` into `IndexedObjectChange`s lazily, one at a time, so that a large
+/// checkpoint's changes can be streamed into batched writes without materializing them all.
+pub fn indexed_object_changes<I>(changes: I) -> impl Iterator<Item = IndexedObjectChange>
+where
+    I: IntoIterator<Item = ObjectChange>,
+{
+    changes.into_iter().map(IndexedObjectChange::from)
+}
+
+/// Derives the status of an object after `change`, given its status before the change (if it
+/// was known). An object that was wrapped shows up again as `Created` or `Mutated` when it is
+/// unwrapped, which would otherwise be indistinguishable from a deletion followed by a fresh
+/// creation; here it transitions to `Unwrapped` instead, or to `UnwrappedThenDeleted` if it is
+/// deleted without ever becoming active again.
+pub fn reconcile_wrap_unwrap(
+    previous_status: Option<ObjectStatus>,
+    change: &IndexedObjectChange,
+) -> ObjectStatus {
+    let was_wrapped = matches!(previous_status, Some(ObjectStatus::Wrapped));
+    match change {
+        IndexedObjectChange::Wrapped { .. } => ObjectStatus::Wrapped,
+        IndexedObjectChange::Deleted { .. } if was_wrapped => ObjectStatus::UnwrappedThenDeleted,
+        IndexedObjectChange::Deleted { .. } => ObjectStatus::Deleted,
+        IndexedObjectChange::Created { .. }
+        | IndexedObjectChange::Mutated { .. }
+        | IndexedObjectChange::Transferred { .. }
+            if was_wrapped =>
+        {
+            ObjectStatus::Unwrapped
+        }
+        IndexedObjectChange::Created { .. } | IndexedObjectChange::Published { .. } => {
+            ObjectStatus::Created
+        }
+        IndexedObjectChange::Mutated { .. } | IndexedObjectChange::Transferred { .. } => {
+            ObjectStatus::Mutated
+        }
+    }
+}
+
+/// Whether an object change adds a coin to, or removes a coin from, an address's balance.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BalanceEffectSign {
+    Positive,
+    Negative,
+}
+
+/// A coin object entering or leaving an address's balance, derived from object changes alone.
+/// Coin values are not known at this point, so this only identifies which coins need to be
+/// accounted for, e.g. to maintain a fallback balance index when full effects aren't available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceEffect {
+    pub object_id: ObjectID,
+    pub coin_type: String,
+    pub owner: SuiAddress,
+    pub sign: BalanceEffectSign,
+}
+
+/// Identifies the coin creations, deletions, wraps and transfers among `changes`. `coin_types`
+/// maps the IDs of coin objects to their coin type; changes to any other object are ignored, as
+/// are coins that are not owned by an address.
+pub fn coin_balance_effects(
+    changes: &[IndexedObjectChange],
+    coin_types: &HashMap<ObjectID, String>,
+) -> Vec<BalanceEffect> {
+    let mut effects = vec![];
+    for change in changes {
+        let (object_id, debited, credited) = match change {
+            IndexedObjectChange::Created {
+                object_id, owner, ..
+            } => (object_id, None, address_owner(owner)),
+            IndexedObjectChange::Transferred {
+                object_id,
+                sender,
+                recipient,
+                ..
+            } => (object_id, Some(*sender), address_owner(recipient)),
+            IndexedObjectChange::Deleted {
+                object_id, sender, ..
+            }
+            | IndexedObjectChange::Wrapped {
+                object_id, sender, ..
+            } => (object_id, Some(*sender), None),
+            IndexedObjectChange::Mutated { .. } | IndexedObjectChange::Published { .. } => continue,
+        };
+        let Some(coin_type) = coin_types.get(object_id) else {
+            continue;
+        };
+        let changes = [
+            (debited, BalanceEffectSign::Negative),
+            (credited, BalanceEffectSign::Positive),
+        ];
+        for (owner, sign) in changes {
+            if let Some(owner) = owner {
+                effects.push(BalanceEffect {
+                    object_id: *object_id,
+                    coin_type: coin_type.clone(),
+                    owner,
+                    sign,
+                });
+            }
+        }
+    }
+    effects
+}
+
+fn address_owner(owner: &Owner) -> Option<SuiAddress> {
+    match owner {
+        Owner::AddressOwner(address) => Some(*address),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    use move_core_types::{
+        account_address::AccountAddress,
+        language_storage::{StructTag, TypeTag},
+    };
+    use sui_json_rpc_types::{BalanceChange, SuiEvent, SuiTransactionBlockEvents};
+    use sui_protocol_config::ProtocolVersion;
+    use sui_test_transaction_builder::TestTransactionBuilder;
+    use sui_types::{
+        base_types::{random_object_ref, ExecutionDigests, MoveObjectType, ObjectID},
+        committee::Committee,
+        crypto::{get_key_pair, AccountKeyPair, AggregateAuthoritySignature, KeypairTraits},
+        digests::{CheckpointDigest, TransactionDigest},
+        effects::TransactionEffects,
+        event::EventID,
+        execution_status::ExecutionStatus,
+        gas::GasCostSummary,
+        gas_coin::GasCoin,
+        governance::StakedSui,
+        message_envelope::Message,
+        messages_checkpoint::{self, CheckpointContents, SignedCheckpointSummary},
+        move_package::UpgradeInfo,
+        object::{Data, MoveObject, Object, Owner},
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::VerifiedTransaction,
+        Identifier,
+    };
+
+    use super::*;
+
+    fn non_coin_object(id: ObjectID) -> Object {
+        let object_type = StructTag {
+            address: AccountAddress::from_hex_literal("0xe7").unwrap(),
+            module: Identifier::new("foo").unwrap(),
+            name: Identifier::new("Foo").unwrap(),
+            type_params: vec![],
+        };
+        object_of_type(id, object_type.into())
+    }
+
+    /// An object of type `object_type` whose contents are just its id.
+    fn object_of_type(id: ObjectID, object_type: MoveObjectType) -> Object {
+        let data = Data::Move(
+            unsafe {
+                MoveObject::new_from_execution_with_limit(
+                    object_type,
+                    true,
+                    1.into(),
+                    bcs::to_bytes(&id).unwrap(),
+                    256,
+                )
+            }
+            .unwrap(),
+        );
+        Object {
+            owner: Owner::AddressOwner(SuiAddress::ZERO),
+            data,
+            previous_transaction: TransactionDigest::genesis(),
+            storage_rebate: 0,
+        }
+    }
+
+    #[test]
+    fn test_partition_coins() {
+        let objects = vec![
+            IndexedObject::from_object(1, Object::new_gas_for_testing(), None, None).unwrap(),
+            IndexedObject::from_object(1, non_coin_object(ObjectID::random()), None, None).unwrap(),
+            IndexedObject::from_object(1, Object::new_gas_for_testing(), None, None).unwrap(),
+            IndexedObject::from_object(1, non_coin_object(ObjectID::random()), None, None).unwrap(),
+        ];
+        let ids = objects.iter().map(|o| o.object_id).collect::<Vec<_>>();
+
+        let (coins, others) = partition_coins(objects);
+
+        assert!(coins.iter().all(|o| o.coin_type.is_some()));
+        assert!(others.iter().all(|o| o.coin_type.is_none()));
+        assert_eq!(
+            coins.iter().map(|o| o.object_id).collect::<Vec<_>>(),
+            vec![ids[0], ids[2]]
+        );
+        assert_eq!(
+            others.iter().map(|o| o.object_id).collect::<Vec<_>>(),
+            vec![ids[1], ids[3]]
+        );
+    }
+
+    #[test]
+    fn test_total_coin_balance() {
+        let owner = SuiAddress::random_for_testing_only();
+        let objects = [
+            Object::with_id_owner_gas_for_testing(ObjectID::random(), owner, u64::MAX),
+            Object::with_id_owner_gas_for_testing(ObjectID::random(), owner, u64::MAX),
+            Object::with_id_owner_gas_for_testing(ObjectID::random(), owner, 2),
+            non_coin_object(ObjectID::random()),
+        ]
+        .into_iter()
+        .map(|object| IndexedObject::from_object(1, object, None, None).unwrap())
+        .collect::<Vec<_>>();
+
+        assert_eq!(objects[0].coin_balance_u128(), Some(u64::MAX as u128));
+        assert_eq!(objects[3].coin_balance_u128(), None);
+        assert_eq!(total_coin_balance(&objects), 2 * u64::MAX as u128 + 2);
+    }
+
+    #[test]
+    fn test_malformed_coin_has_no_balance() {
+        // A `Coin<SUI>` whose contents stop after the id, with no balance.
+        let object = object_of_type(ObjectID::random(), GasCoin::type_().into());
+
+        let indexed = IndexedObject::from_object(1, object, None, None).unwrap();
+        assert!(indexed.coin_type.is_some());
+        assert_eq!(indexed.coin_balance, None);
+    }
+
+    #[test]
+    fn test_indexed_package() {
+        let dependency = ObjectID::random();
+        let upgraded_dependency = ObjectID::random();
+        let linkage_table = BTreeMap::from([(
+            dependency,
+            UpgradeInfo {
+                upgraded_id: upgraded_dependency,
+                upgraded_version: SequenceNumber::from_u64(3),
+            },
+        )]);
+        let module_map = BTreeMap::from([
+            ("b".to_string(), vec![2]),
+            ("a".to_string(), vec![1]),
+            ("c".to_string(), vec![3]),
+        ]);
+        let move_package = MovePackage::new(
+            ObjectID::random(),
+            SequenceNumber::from_u64(2),
+            module_map,
+            u64::MAX,
+            vec![],
+            linkage_table,
+        )
+        .unwrap();
+
+        let package = IndexedPackage::from_package(7, move_package.clone());
+        assert_eq!(package.package_id, move_package.id());
+        assert_eq!(package.checkpoint_sequence_number, 7);
+        assert_eq!(package.version, 2);
+        assert_eq!(package.modules, vec!["a", "b", "c"]);
+        assert_eq!(
+            package.linkage,
+            BTreeMap::from([(
+                dependency,
+                (upgraded_dependency, SequenceNumber::from_u64(3))
+            )])
+        );
+    }
+
+    #[test]
+    fn test_classify() {
+        let classify = |object, df_info| {
+            IndexedObject::from_object(1, object, None, df_info)
+                .unwrap()
+                .classify()
+        };
+        let owner = SuiAddress::random_for_testing_only();
+        let df_info = DynamicFieldInfo {
+            name: DynamicFieldName {
+                type_: TypeTag::U64,
+                value: serde_json::json!(1),
+            },
+            bcs_name: vec![],
+            type_: DynamicFieldType::DynamicField,
+            object_type: "u64".to_string(),
+            object_id: ObjectID::random(),
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::random(),
+        };
+
+        assert_eq!(
+            classify(
+                Object::with_id_owner_gas_for_testing(ObjectID::random(), owner, 10),
+                None
+            ),
+            ObjectClass::Coin
+        );
+        assert_eq!(
+            classify(
+                object_of_type(ObjectID::random(), StakedSui::type_().into()),
+                None
+            ),
+            ObjectClass::StakedSui
+        );
+        assert_eq!(
+            classify(non_coin_object(ObjectID::random()), None),
+            ObjectClass::Other
+        );
+        assert_eq!(
+            classify(non_coin_object(ObjectID::random()), Some(df_info)),
+            ObjectClass::DynamicField
+        );
+    }
+
+    #[test]
+    fn test_parent_object_id() {
+        let parent = ObjectID::random();
+        let mut child = non_coin_object(ObjectID::random());
+        child.owner = Owner::ObjectOwner(parent.into());
+        let df_info = DynamicFieldInfo {
+            name: DynamicFieldName {
+                type_: TypeTag::U64,
+                value: serde_json::json!(1),
+            },
+            bcs_name: vec![],
+            type_: DynamicFieldType::DynamicField,
+            object_type: "u64".to_string(),
+            object_id: child.id(),
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::random(),
+        };
+
+        let child = IndexedObject::from_object(1, child, None, Some(df_info)).unwrap();
+        assert_eq!(child.parent_object_id, Some(parent));
+        assert_eq!(child.owner_id, Some(parent.into()));
+
+        let owned =
+            IndexedObject::from_object(1, non_coin_object(ObjectID::random()), None, None).unwrap();
+        assert_eq!(owned.parent_object_id, None);
+    }
+
+    #[test]
+    fn test_indexed_dynamic_field() {
+        let parent = ObjectID::random();
+        let mut field = non_coin_object(ObjectID::random());
+        field.owner = Owner::ObjectOwner(parent.into());
+        let df_info = |type_, object_id, object_type: &str| DynamicFieldInfo {
+            name: DynamicFieldName {
+                type_: TypeTag::U64,
+                value: serde_json::json!(7),
+            },
+            bcs_name: bcs::to_bytes(&7u64).unwrap(),
+            type_,
+            object_type: object_type.to_string(),
+            object_id,
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::random(),
+        };
+
+        let dynamic_field = IndexedDynamicField::from_object(
+            parent,
+            &field,
+            &df_info(DynamicFieldType::DynamicField, field.id(), "u64"),
+        );
+        assert_eq!(
+            dynamic_field,
+            IndexedDynamicField {
+                parent_object_id: parent,
+                field_object_id: field.id(),
+                field_object_version: 1,
+                kind: DynamicFieldType::DynamicField,
+                name_type: "u64".to_string(),
+                name_bcs: bcs::to_bytes(&7u64).unwrap(),
+                value_type: "u64".to_string(),
+                value_object_id: None,
+            }
+        );
+
+        let child = ObjectID::random();
+        let dynamic_object = IndexedDynamicField::from_object(
+            parent,
+            &field,
+            &df_info(DynamicFieldType::DynamicObject, child, "0x42::nft::NFT"),
+        );
+        assert_eq!(dynamic_object.field_object_id, field.id());
+        assert_eq!(dynamic_object.kind, DynamicFieldType::DynamicObject);
+        assert_eq!(dynamic_object.value_type, "0x42::nft::NFT");
+        assert_eq!(dynamic_object.value_object_id, Some(child));
+    }
+
+    #[test]
+    fn test_checkpoint_summary() {
+        let checkpoint = IndexedCheckpoint {
+            sequence_number: 42,
+            checkpoint_digest: CheckpointDigest::random(),
+            epoch: 3,
+            tx_digests: vec![TransactionDigest::random(), TransactionDigest::random()],
+            network_total_transactions: 100,
+            previous_checkpoint_digest: Some(CheckpointDigest::random()),
+            timestamp_ms: 1_700_000_000_000,
+            total_gas_cost: 10,
+            computation_cost: 10,
+            storage_cost: 0,
+            storage_rebate: 0,
+            non_refundable_storage_fee: 0,
+            checkpoint_commitments: vec![],
+            validator_signature: AggregateAuthoritySignature::default(),
+            successful_tx_num: 2,
+            end_of_epoch_data: None,
+            end_of_epoch: false,
+        };
+
+        let summary = checkpoint.summary();
+
+        assert_eq!(summary.sequence_number, checkpoint.sequence_number);
+        assert_eq!(summary.checkpoint_digest, checkpoint.checkpoint_digest);
+        assert_eq!(summary.epoch, checkpoint.epoch);
+        assert_eq!(summary.timestamp_ms, checkpoint.timestamp_ms);
+        assert_eq!(summary.tx_count, checkpoint.tx_digests.len());
+    }
+
+    /// A checkpoint certified by a fresh test committee, with its contents.
+    fn certified_checkpoint(
+        epoch_rolling_gas_cost_summary: GasCostSummary,
+        end_of_epoch_data: Option<EndOfEpochData>,
+    ) -> (CertifiedCheckpointSummary, CheckpointContents) {
+        let (committee, keys) = Committee::new_simple_test_committee();
+        let contents =
+            CheckpointContents::new_with_digests_only_for_tests([ExecutionDigests::random()]);
+        let summary = messages_checkpoint::CheckpointSummary::new(
+            committee.epoch,
+            10,
+            100,
+            &contents,
+            None,
+            epoch_rolling_gas_cost_summary,
+            end_of_epoch_data,
+            0,
+        );
+        let sign_infos = keys
+            .iter()
+            .map(|key| {
+                SignedCheckpointSummary::sign(committee.epoch, &summary, key, key.public().into())
+            })
+            .collect::<Vec<_>>();
+        let checkpoint = CertifiedCheckpointSummary::new(summary, sign_infos, &committee).unwrap();
+        (checkpoint, contents)
+    }
+
+    #[test]
+    fn test_total_gas_cost_saturates() {
+        let near_max = u64::MAX - 1;
+        let (checkpoint, contents) =
+            certified_checkpoint(GasCostSummary::new(near_max, near_max, 0, 0), None);
+        let indexed = IndexedCheckpoint::from_sui_checkpoint(&checkpoint, &contents, 0);
+        assert_eq!(indexed.total_gas_cost, i64::MAX);
+        assert_eq!(indexed.computation_cost, near_max);
+
+        let (checkpoint, contents) =
+            certified_checkpoint(GasCostSummary::new(0, 0, near_max, 0), None);
+        let indexed = IndexedCheckpoint::from_sui_checkpoint(&checkpoint, &contents, 0);
+        assert_eq!(indexed.total_gas_cost, i64::MIN);
+
+        let (checkpoint, contents) =
+            certified_checkpoint(GasCostSummary::new(1_000, 200, 300, 0), None);
+        let indexed = IndexedCheckpoint::from_sui_checkpoint(&checkpoint, &contents, 0);
+        assert_eq!(indexed.total_gas_cost, 900);
+    }
+
+    #[test]
+    fn test_end_of_epoch_checkpoint() {
+        let committee = Committee::new_simple_test_committee().0;
+        let end_of_epoch_data = EndOfEpochData {
+            next_epoch_committee: committee.voting_rights.clone(),
+            next_epoch_protocol_version: ProtocolVersion::MAX,
+            epoch_commitments: vec![],
+        };
+        let (checkpoint, contents) =
+            certified_checkpoint(GasCostSummary::default(), Some(end_of_epoch_data.clone()));
+
+        let indexed = IndexedCheckpoint::from_sui_checkpoint(&checkpoint, &contents, 0);
+        assert!(indexed.end_of_epoch);
+        assert_eq!(indexed.end_of_epoch_data, Some(end_of_epoch_data));
+        assert_eq!(
+            indexed.end_of_epoch_data.unwrap().next_epoch_committee,
+            committee.voting_rights
+        );
+    }
+
+    #[test]
+    fn test_coin_balance_effects() {
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let transferred_coin = ObjectID::random();
+        let deleted_coin = ObjectID::random();
+        let non_coin = ObjectID::random();
+        let coin_type = GasCoin::type_();
+        let coin_types = HashMap::from([
+            (transferred_coin, coin_type.to_canonical_string()),
+            (deleted_coin, coin_type.to_canonical_string()),
+        ]);
+        let changes = vec![
+            IndexedObjectChange::Transferred {
+                sender,
+                recipient: Owner::AddressOwner(recipient),
+                object_type: coin_type.clone(),
+                object_id: transferred_coin,
+                version: SequenceNumber::from_u64(2),
+                digest: ObjectDigest::random(),
+            },
+            IndexedObjectChange::Deleted {
+                sender,
+                object_type: coin_type.clone(),
+                object_id: deleted_coin,
+                version: SequenceNumber::from_u64(2),
+            },
+            IndexedObjectChange::Deleted {
+                sender,
+                object_type: coin_type,
+                object_id: non_coin,
+                version: SequenceNumber::from_u64(2),
+            },
+        ];
+
+        let effects = coin_balance_effects(&changes, &coin_types);
+
+        let effect = |object_id, owner, sign| BalanceEffect {
+            object_id,
+            coin_type: coin_types[&object_id].clone(),
+            owner,
+            sign,
+        };
+        assert_eq!(
+            effects,
+            vec![
+                effect(transferred_coin, sender, BalanceEffectSign::Negative),
+                effect(transferred_coin, recipient, BalanceEffectSign::Positive),
+                effect(deleted_coin, sender, BalanceEffectSign::Negative),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reconcile_wrap_then_unwrap() {
+        let sender = SuiAddress::random_for_testing_only();
+        let object_id = ObjectID::random();
+        let object_type = GasCoin::type_();
+        let wrapped = IndexedObjectChange::Wrapped {
+            sender,
+            object_type: object_type.clone(),
+            object_id,
+            version: SequenceNumber::from_u64(2),
+        };
+        let unwrapped = IndexedObjectChange::Mutated {
+            sender,
+            owner: Owner::AddressOwner(sender),
+            object_type,
+            object_id,
+            version: SequenceNumber::from_u64(3),
+            previous_version: SequenceNumber::from_u64(2),
+            digest: ObjectDigest::random(),
+        };
+
+        let status = reconcile_wrap_unwrap(Some(ObjectStatus::Created), &wrapped);
+        assert_eq!(status, ObjectStatus::Wrapped);
+        let status = reconcile_wrap_unwrap(Some(status), &unwrapped);
+        assert_eq!(status, ObjectStatus::Unwrapped);
+        // Once active again, further mutations are ordinary.
+        assert_eq!(
+            reconcile_wrap_unwrap(Some(status), &unwrapped),
+            ObjectStatus::Mutated
+        );
+    }
+
+    #[test]
+    fn test_reconcile_wrap_then_delete() {
+        let sender = SuiAddress::random_for_testing_only();
+        let object_id = ObjectID::random();
+        let object_type = GasCoin::type_();
+        let wrapped = IndexedObjectChange::Wrapped {
+            sender,
+            object_type: object_type.clone(),
+            object_id,
+            version: SequenceNumber::from_u64(2),
+        };
+        let deleted = IndexedObjectChange::Deleted {
+            sender,
+            object_type,
+            object_id,
+            version: SequenceNumber::from_u64(3),
+        };
+
+        let status = reconcile_wrap_unwrap(None, &wrapped);
+        assert_eq!(status, ObjectStatus::Wrapped);
+        assert_eq!(
+            reconcile_wrap_unwrap(Some(status), &deleted),
+            ObjectStatus::UnwrappedThenDeleted
+        );
+        assert_eq!(reconcile_wrap_unwrap(None, &deleted), ObjectStatus::Deleted);
+    }
+
+    #[test]
+    fn test_object_change_rows() {
+        let sender = SuiAddress::random_for_testing_only();
+        let object_changes = (1..=3)
+            .map(|version| IndexedObjectChange::Created {
+                sender,
+                owner: Owner::AddressOwner(sender),
+                object_type: GasCoin::type_(),
+                object_id: ObjectID::random(),
+                version: SequenceNumber::from_u64(version),
+                digest: ObjectDigest::random(),
+            })
+            .collect::<Vec<_>>();
+        let transaction = IndexedTransaction {
+            tx_sequence_number: 7,
+            tx_digest: TransactionDigest::random(),
+            sender_signed_data: VerifiedTransaction::new_genesis_transaction(vec![])
+                .into_inner()
+                .into_data(),
+            effects: TransactionEffects::default(),
+            checkpoint_sequence_number: 1,
+            timestamp_ms: 0,
+            object_changes,
+            balance_change: vec![],
+            events: vec![],
+            transaction_kind: TransactionKind::SystemTransaction(SystemTransactionKind::Genesis),
+            successful_tx_num: 0,
+            computation_cost: 0,
+            storage_cost: 0,
+            storage_rebate: 0,
+            non_refundable_storage_fee: 0,
+        };
+
+        let eager = transaction
+            .object_changes
+            .iter()
+            .map(|change| change.to_db_row(transaction.tx_sequence_number))
+            .collect::<IndexerResult<Vec<_>>>()
+            .unwrap();
+        let streamed = transaction
+            .object_change_rows()
+            .collect::<IndexerResult<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(streamed, eager);
+        assert_eq!(
+            streamed[1].object_id,
+            transaction.object_changes[1].object_id()
+        );
+        assert_eq!(streamed[1].version, SequenceNumber::from_u64(2));
+    }
+
+    #[test]
+    fn test_object_change_round_trip() {
+        let sender = SuiAddress::random_for_testing_only();
+        let object_type = StructTag::from_str("0x42::nft::NFT").unwrap();
+        let version = SequenceNumber::from_u64(5);
+        let object_changes = vec![
+            ObjectChange::Published {
+                package_id: ObjectID::random(),
+                version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::random(),
+                modules: vec!["a".to_string(), "b".to_string()],
+            },
+            ObjectChange::Transferred {
+                sender,
+                recipient: Owner::AddressOwner(SuiAddress::random_for_testing_only()),
+                object_type: object_type.clone(),
+                object_id: ObjectID::random(),
+                version,
+                digest: ObjectDigest::random(),
+            },
+            ObjectChange::Mutated {
+                sender,
+                owner: Owner::Shared {
+                    initial_shared_version: SequenceNumber::from_u64(2),
+                },
+                object_type: object_type.clone(),
+                object_id: ObjectID::random(),
+                version,
+                previous_version: SequenceNumber::from_u64(4),
+                digest: ObjectDigest::random(),
+            },
+            ObjectChange::Deleted {
+                sender,
+                object_type: object_type.clone(),
+                object_id: ObjectID::random(),
+                version,
+            },
+            ObjectChange::Wrapped {
+                sender,
+                object_type: object_type.clone(),
+                object_id: ObjectID::random(),
+                version,
+            },
+            ObjectChange::Created {
+                sender,
+                owner: Owner::ObjectOwner(SuiAddress::random_for_testing_only()),
+                object_type,
+                object_id: ObjectID::random(),
+                version,
+                digest: ObjectDigest::random(),
+            },
+        ];
+
+        // Deliberately exhaustive: a new `ObjectChange` variant won't compile until it is covered
+        // above and by the conversions.
+        let variants = object_changes
+            .iter()
+            .map(|change| match change {
+                ObjectChange::Published { .. } => 0,
+                ObjectChange::Transferred { .. } => 1,
+                ObjectChange::Mutated { .. } => 2,
+                ObjectChange::Deleted { .. } => 3,
+                ObjectChange::Wrapped { .. } => 4,
+                ObjectChange::Created { .. } => 5,
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(variants.len(), 6);
+
+        for change in object_changes {
+            let indexed = IndexedObjectChange::from(change.clone());
+            // The indexed form is what gets persisted, so it has to survive bcs as well.
+            let indexed: IndexedObjectChange =
+                bcs::from_bytes(&bcs::to_bytes(&indexed).unwrap()).unwrap();
+            assert_eq!(ObjectChange::from(indexed), change);
+        }
+    }
+
+    #[test]
+    fn test_indexed_object_changes_streams() {
+        let sender = SuiAddress::random_for_testing_only();
+        let object_changes = (1..=5)
+            .map(|version| ObjectChange::Created {
+                sender,
+                owner: Owner::AddressOwner(sender),
+                object_type: GasCoin::type_(),
+                object_id: ObjectID::random(),
+                version: SequenceNumber::from_u64(version),
+                digest: ObjectDigest::random(),
+            })
+            .collect::<Vec<_>>();
+        let eager = object_changes
+            .iter()
+            .cloned()
+            .map(IndexedObjectChange::from)
+            .collect::<Vec<_>>();
+
+        // Consumed in batches, as a writer would.
+        let mut streamed = indexed_object_changes(object_changes);
+        let mut batches = vec![];
+        loop {
+            let batch = streamed.by_ref().take(2).collect::<Vec<_>>();
+            if batch.is_empty() {
+                break;
+            }
+            batches.push(batch);
+        }
+
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches.concat(), eager);
+    }
+
+    #[test]
+    fn test_owner_to_canonical_string() {
+        let address = SuiAddress::random_for_testing_only();
+        assert_eq!(
+            owner_to_canonical_string(&Owner::AddressOwner(address)),
+            format!("address:{address}")
+        );
+        assert_eq!(
+            owner_to_canonical_string(&Owner::ObjectOwner(address)),
+            format!("object:{address}")
+        );
+        assert_eq!(
+            owner_to_canonical_string(&Owner::Shared {
+                initial_shared_version: SequenceNumber::from_u64(5)
+            }),
+            "shared:5"
+        );
+        assert_eq!(owner_to_canonical_string(&Owner::Immutable), "immutable");
+    }
+
+    #[test]
+    fn test_finalize_epoch() {
+        let start = IndexedEpochInfo {
+            epoch: 3,
+            first_checkpoint_id: 100,
+            epoch_start_timestamp: 1_000,
+            reference_gas_price: 750,
+            protocol_version: 12,
+            ..Default::default()
+        };
+        let end = IndexedEpochInfo {
+            epoch: 3,
+            epoch_total_transactions: Some(42),
+            last_checkpoint_id: Some(199),
+            epoch_end_timestamp: Some(2_000),
+            storage_fund_reinvestment: Some(1),
+            storage_charge: Some(2),
+            storage_rebate: Some(3),
+            storage_fund_balance: Some(4),
+            stake_subsidy_amount: Some(5),
+            total_gas_fees: Some(6),
+            total_stake_rewards_distributed: Some(7),
+            leftover_storage_fund_inflow: Some(8),
+            new_total_stake: Some(9),
+            epoch_commitments: Some(vec![]),
+            next_epoch_reference_gas_price: Some(800),
+            next_epoch_protocol_version: Some(13),
+            ..Default::default()
+        };
+
+        let epoch = finalize_epoch(start, &end);
+
+        assert_eq!(epoch.epoch, 3);
+        assert_eq!(epoch.first_checkpoint_id, 100);
+        assert_eq!(epoch.epoch_start_timestamp, 1_000);
+        assert_eq!(epoch.reference_gas_price, 750);
+        assert_eq!(epoch.protocol_version, 12);
+        assert_eq!(epoch.epoch_total_transactions, Some(42));
+        assert_eq!(epoch.last_checkpoint_id, Some(199));
+        assert_eq!(epoch.epoch_end_timestamp, Some(2_000));
+        assert_eq!(epoch.storage_fund_reinvestment, Some(1));
+        assert_eq!(epoch.storage_charge, Some(2));
+        assert_eq!(epoch.storage_rebate, Some(3));
+        assert_eq!(epoch.storage_fund_balance, Some(4));
+        assert_eq!(epoch.stake_subsidy_amount, Some(5));
+        assert_eq!(epoch.total_gas_fees, Some(6));
+        assert_eq!(epoch.total_stake_rewards_distributed, Some(7));
+        assert_eq!(epoch.leftover_storage_fund_inflow, Some(8));
+        assert_eq!(epoch.new_total_stake, Some(9));
+        assert!(epoch.epoch_commitments.is_some());
+        assert_eq!(epoch.next_epoch_reference_gas_price, Some(800));
+        assert_eq!(epoch.next_epoch_protocol_version, Some(13));
+    }
+
+    #[test]
+    fn test_dynamic_field_name() {
+        let df_info = |value: serde_json::Value| DynamicFieldInfo {
+            name: DynamicFieldName {
+                type_: TypeTag::U64,
+                value,
+            },
+            bcs_name: vec![],
+            type_: DynamicFieldType::DynamicField,
+            object_type: "u64".to_string(),
+            object_id: ObjectID::random(),
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::random(),
+        };
+        let df_name = |df_info| {
+            IndexedObject::from_object(1, non_coin_object(ObjectID::random()), None, df_info)
+                .unwrap()
+                .df_name
+        };
+
+        assert_eq!(
+            df_name(Some(df_info(serde_json::json!("key")))),
+            Some("key".to_string())
+        );
+        assert_eq!(
+            df_name(Some(df_info(serde_json::json!(42)))),
+            Some("42".to_string())
+        );
+        assert_eq!(df_name(Some(df_info(serde_json::Value::Null))), None);
+        assert_eq!(df_name(None), None);
+    }
+
+    #[test]
+    fn test_net_sui_for() {
+        let address = SuiAddress::random_for_testing_only();
+        let other = SuiAddress::random_for_testing_only();
+        let balance_change = |owner, coin_type: TypeTag, amount| BalanceChange {
+            owner: Owner::AddressOwner(owner),
+            coin_type,
+            amount,
+        };
+        let non_sui = TypeTag::Struct(Box::new(StructTag::from_str("0x42::coin::USD").unwrap()));
+        let transaction = IndexedTransaction {
+            tx_sequence_number: 7,
+            tx_digest: TransactionDigest::random(),
+            sender_signed_data: VerifiedTransaction::new_genesis_transaction(vec![])
+                .into_inner()
+                .into_data(),
+            effects: TransactionEffects::default(),
+            checkpoint_sequence_number: 1,
+            timestamp_ms: 0,
+            object_changes: vec![],
+            balance_change: vec![
+                // Transfer in, then gas paid by the same address.
+                balance_change(address, GAS::type_tag(), 1_000),
+                balance_change(address, GAS::type_tag(), -30),
+                balance_change(address, non_sui, 5),
+                balance_change(other, GAS::type_tag(), -1_000),
+            ],
+            events: vec![],
+            transaction_kind: TransactionKind::ProgrammableTransaction,
+            successful_tx_num: 0,
+            computation_cost: 0,
+            storage_cost: 0,
+            storage_rebate: 0,
+            non_refundable_storage_fee: 0,
+        };
+
+        assert_eq!(transaction.net_sui_for(&address), 970);
+        assert_eq!(transaction.net_sui_for(&other), -1_000);
+        assert_eq!(
+            transaction.net_sui_for(&SuiAddress::random_for_testing_only()),
+            0
+        );
+    }
+
+    #[test]
+    fn test_balance_changes_from_object_changes() {
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let (a, b, c, nft) = (
+            ObjectID::random(),
+            ObjectID::random(),
+            ObjectID::random(),
+            ObjectID::random(),
+        );
+
+        // Split 30 off coin `a` and send it to the recipient as `c`, then merge coin `b` into `a`.
+        let input_objects = vec![
+            Object::with_id_owner_gas_for_testing(a, sender, 100),
+            Object::with_id_owner_gas_for_testing(b, sender, 50),
+            non_coin_object(nft),
+        ];
+        let output_objects = vec![
+            Object::with_id_owner_gas_for_testing(a, sender, 120),
+            Object::with_id_owner_gas_for_testing(c, recipient, 30),
+            non_coin_object(nft),
+        ];
+        let version = SequenceNumber::from_u64(2);
+        let object_changes = vec![
+            IndexedObjectChange::Mutated {
+                sender,
+                owner: Owner::AddressOwner(sender),
+                object_type: GasCoin::type_(),
+                object_id: a,
+                version,
+                previous_version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::random(),
+            },
+            IndexedObjectChange::Deleted {
+                sender,
+                object_type: GasCoin::type_(),
+                object_id: b,
+                version,
+            },
+            IndexedObjectChange::Created {
+                sender,
+                owner: Owner::AddressOwner(recipient),
+                object_type: GasCoin::type_(),
+                object_id: c,
+                version,
+                digest: ObjectDigest::random(),
+            },
+            // Not a coin, so it doesn't contribute.
+            IndexedObjectChange::Transferred {
+                sender,
+                recipient: Owner::AddressOwner(recipient),
+                object_type: StructTag::from_str("0x42::nft::NFT").unwrap(),
+                object_id: nft,
+                version,
+                digest: ObjectDigest::random(),
+            },
+        ];
+        let transaction = IndexedTransaction {
+            tx_sequence_number: 7,
+            tx_digest: TransactionDigest::random(),
+            sender_signed_data: VerifiedTransaction::new_genesis_transaction(vec![])
+                .into_inner()
+                .into_data(),
+            effects: TransactionEffects::default(),
+            checkpoint_sequence_number: 1,
+            timestamp_ms: 0,
+            object_changes,
+            balance_change: vec![],
+            events: vec![],
+            transaction_kind: TransactionKind::ProgrammableTransaction,
+            successful_tx_num: 0,
+            computation_cost: 0,
+            storage_cost: 0,
+            storage_rebate: 0,
+            non_refundable_storage_fee: 0,
+        };
+
+        let mut expected = vec![
+            BalanceChange {
+                owner: Owner::AddressOwner(sender),
+                coin_type: GAS::type_tag(),
+                amount: -30,
+            },
+            BalanceChange {
+                owner: Owner::AddressOwner(recipient),
+                coin_type: GAS::type_tag(),
+                amount: 30,
+            },
+        ];
+        expected.sort_by_key(|change| change.owner);
+        assert_eq!(
+            transaction.balance_changes_from_object_changes(&input_objects, &output_objects),
+            expected
+        );
+
+        // A merge on its own leaves the owner's balance unchanged.
+        assert_eq!(
+            IndexedTransaction {
+                object_changes: transaction.object_changes[..2].to_vec(),
+                ..transaction
+            }
+            .balance_changes_from_object_changes(
+                &input_objects,
+                &[Object::with_id_owner_gas_for_testing(a, sender, 150)]
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_touched_packages() {
+        let called = ObjectID::random();
+        let published = ObjectID::random();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.programmable_move_call(
+            called,
+            Identifier::new("counter").unwrap(),
+            Identifier::new("increment").unwrap(),
+            vec![],
+            vec![],
+        );
+        builder.programmable_move_call(
+            called,
+            Identifier::new("counter").unwrap(),
+            Identifier::new("reset").unwrap(),
+            vec![],
+            vec![],
+        );
+        // Also calls `0x2::package::make_immutable` on the upgrade cap.
+        builder.publish_immutable(vec![vec![1, 2, 3]], vec![called]);
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        let transaction = TestTransactionBuilder::new(sender, random_object_ref(), 1000)
+            .programmable(builder.finish())
+            .build_and_sign(&keypair);
+        let transaction = IndexedTransaction {
+            tx_sequence_number: 7,
+            tx_digest: *transaction.digest(),
+            sender_signed_data: transaction.into_data(),
+            effects: TransactionEffects::default(),
+            checkpoint_sequence_number: 1,
+            timestamp_ms: 0,
+            object_changes: vec![IndexedObjectChange::Published {
+                package_id: published,
+                version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::random(),
+                modules: vec!["counter".to_string()],
+            }],
+            balance_change: vec![],
+            events: vec![],
+            transaction_kind: TransactionKind::ProgrammableTransaction,
+            successful_tx_num: 0,
+            computation_cost: 0,
+            storage_cost: 0,
+            storage_rebate: 0,
+            non_refundable_storage_fee: 0,
+        };
+
+        assert_eq!(
+            transaction.touched_packages(),
+            BTreeSet::from([called, published, ObjectID::from_single_byte(2)])
+        );
+    }
+
+    #[test]
+    fn test_is_simple_transfer() {
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        let gas = random_object_ref();
+        let indexed = |sender_signed_data| IndexedTransaction {
+            tx_sequence_number: 0,
+            tx_digest: TransactionDigest::random(),
+            sender_signed_data,
+            effects: TransactionEffects::default(),
+            checkpoint_sequence_number: 0,
+            timestamp_ms: 0,
+            object_changes: vec![],
+            balance_change: vec![],
+            events: vec![],
+            transaction_kind: TransactionKind::ProgrammableTransaction,
+            successful_tx_num: 0,
+            computation_cost: 0,
+            storage_cost: 0,
+            storage_rebate: 0,
+            non_refundable_storage_fee: 0,
+        };
+
+        let transfer = TestTransactionBuilder::new(sender, gas, 1000)
+            .transfer_sui(Some(10), SuiAddress::random_for_testing_only())
+            .build_and_sign(&keypair);
+        assert!(indexed(transfer.into_data()).is_simple_transfer());
+
+        let move_call = TestTransactionBuilder::new(sender, gas, 1000)
+            .call_counter_create(ObjectID::random())
+            .build_and_sign(&keypair);
+        assert!(!indexed(move_call.into_data()).is_simple_transfer());
+
+        let genesis = VerifiedTransaction::new_genesis_transaction(vec![])
+            .into_inner()
+            .into_data();
+        assert!(!indexed(genesis).is_simple_transfer());
+    }
+
+    #[test]
+    fn test_to_tx_index() {
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        let recipient = SuiAddress::random_for_testing_only();
+        let package = ObjectID::random();
+        let gas = random_object_ref();
+        let created = random_object_ref();
+        let transaction = TestTransactionBuilder::new(sender, gas, 1000)
+            .call_counter_create(package)
+            .build_and_sign(&keypair);
+        let tx_digest = *transaction.digest();
+        let sender_signed_data = transaction.into_data();
+        let effects = TransactionEffects::new_from_execution_v1(
+            ExecutionStatus::Success,
+            0,
+            GasCostSummary::new(1_000, 2_000, 300, 20),
+            vec![],
+            vec![],
+            tx_digest,
+            vec![(created, Owner::AddressOwner(recipient))],
+            vec![(gas, Owner::AddressOwner(sender))],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            (gas, Owner::AddressOwner(sender)),
+            None,
+            vec![],
+        );
+        let gas_cost_summary = effects.gas_cost_summary().clone();
+        let transaction = IndexedTransaction {
+            tx_sequence_number: 7,
+            tx_digest,
+            sender_signed_data,
+            effects,
+            checkpoint_sequence_number: 3,
+            timestamp_ms: 0,
+            object_changes: vec![],
+            balance_change: vec![],
+            events: vec![],
+            transaction_kind: TransactionKind::ProgrammableTransaction,
+            successful_tx_num: 1,
+            computation_cost: gas_cost_summary.computation_cost,
+            storage_cost: gas_cost_summary.storage_cost,
+            storage_rebate: gas_cost_summary.storage_rebate,
+            non_refundable_storage_fee: gas_cost_summary.non_refundable_storage_fee,
+        };
+
+        assert_eq!(transaction.computation_cost, 1_000);
+        assert_eq!(transaction.storage_cost, 2_000);
+        assert_eq!(transaction.storage_rebate, 300);
+        assert_eq!(transaction.non_refundable_storage_fee, 20);
+
+        assert_eq!(
+            transaction.to_tx_index(),
+            TxIndex {
+                tx_sequence_number: 7,
+                transaction_digest: tx_digest,
+                checkpoint_sequence_number: 3,
+                input_objects: vec![package, gas.0],
+                changed_objects: vec![gas.0, created.0],
+                payers: vec![sender],
+                senders: vec![sender],
+                recipients: vec![sender, recipient],
+                move_calls: vec![TxMoveCall {
+                    package,
+                    module: "counter".to_string(),
+                    function: "create".to_string(),
+                    type_arguments: vec![],
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_index_transactions() {
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        let programmable = TestTransactionBuilder::new(sender, random_object_ref(), 1000)
+            .call_counter_create(ObjectID::random())
+            .build_and_sign(&keypair)
+            .into_data();
+        let genesis = VerifiedTransaction::new_genesis_transaction(vec![])
+            .into_inner()
+            .into_data();
+        let transactions = vec![programmable.clone(), genesis.clone()];
+        let effects = vec![
+            TransactionEffects::new_with_tx(&programmable),
+            TransactionEffects::new_with_tx(&genesis),
+        ];
+        let object_change = IndexedObjectChange::Deleted {
+            sender,
+            object_type: GasCoin::type_(),
+            object_id: ObjectID::random(),
+            version: SequenceNumber::from_u64(2),
+        };
+        let changes = vec![(vec![], vec![object_change.clone()]), (vec![], vec![])];
+
+        let indexed = index_transactions(
+            3,
+            1_000,
+            10,
+            transactions.clone(),
+            effects.clone(),
+            vec![vec![], vec![]],
+            changes.clone(),
+        )
+        .unwrap();
+        assert_eq!(indexed.len(), 2);
+        assert_eq!(indexed[0].tx_sequence_number, 10);
+        assert_eq!(indexed[1].tx_sequence_number, 11);
+        assert_eq!(indexed[0].tx_digest, programmable.digest());
+        assert_eq!(indexed[1].tx_digest, genesis.digest());
+        assert!(indexed
+            .iter()
+            .all(|tx| tx.checkpoint_sequence_number == 3 && tx.timestamp_ms == 1_000));
+        assert_eq!(
+            indexed[0].transaction_kind,
+            TransactionKind::ProgrammableTransaction
+        );
+        assert_eq!(
+            indexed[1].transaction_kind,
+            TransactionKind::SystemTransaction(SystemTransactionKind::Genesis)
+        );
+        assert_eq!(indexed[0].successful_tx_num, 1);
+        assert_eq!(indexed[0].object_changes, vec![object_change]);
+        assert!(indexed[1].object_changes.is_empty());
+
+        // One event list short.
+        assert!(matches!(
+            index_transactions(3, 1_000, 10, transactions, effects, vec![vec![]], changes),
+            Err(IndexerError::InvalidArgumentError(_))
+        ));
+    }
+
+    #[test]
+    fn test_system_transaction_kinds() {
+        let kinds = [
+            (
+                VerifiedTransaction::new_change_epoch(
+                    1,
+                    ProtocolVersion::MIN,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    vec![],
+                ),
+                SystemTransactionKind::ChangeEpoch,
+            ),
+            (
+                VerifiedTransaction::new_genesis_transaction(vec![]),
+                SystemTransactionKind::Genesis,
+            ),
+            (
+                VerifiedTransaction::new_consensus_commit_prologue(1, 2, 3),
+                SystemTransactionKind::ConsensusCommitPrologue,
+            ),
+            (
+                VerifiedTransaction::new_authenticator_state_update(
+                    1,
+                    2,
+                    vec![],
+                    SequenceNumber::from_u64(1),
+                ),
+                SystemTransactionKind::AuthenticatorStateUpdate,
+            ),
+            (
+                VerifiedTransaction::new_randomness_state_update(
+                    1,
+                    2,
+                    3,
+                    vec![],
+                    SequenceNumber::from_u64(1),
+                ),
+                SystemTransactionKind::RandomnessStateUpdate,
+            ),
+            (
+                VerifiedTransaction::new_end_of_epoch_transaction(vec![]),
+                SystemTransactionKind::EndOfEpochTransaction,
+            ),
+        ];
+        for (transaction, system_kind) in kinds {
+            let kind = TransactionKind::from(transaction.data().transaction_data().kind());
+            assert_eq!(kind, TransactionKind::SystemTransaction(system_kind));
+            assert_eq!(kind.as_i16(), 0);
+        }
+
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        let programmable = TestTransactionBuilder::new(sender, random_object_ref(), 1000)
+            .call_counter_create(ObjectID::random())
+            .build_and_sign(&keypair);
+        let kind = TransactionKind::from(programmable.data().transaction_data().kind());
+        assert_eq!(kind, TransactionKind::ProgrammableTransaction);
+        assert_eq!(kind.as_i16(), 1);
+    }
+
+    #[test]
+    fn test_changes_from_response() {
+        let sender = SuiAddress::random_for_testing_only();
+        let digest = TransactionDigest::random();
+        let object_change = ObjectChange::Wrapped {
+            sender,
+            object_type: GasCoin::type_(),
+            object_id: ObjectID::random(),
+            version: SequenceNumber::from_u64(3),
+        };
+        let balance_change = BalanceChange {
+            owner: Owner::AddressOwner(sender),
+            coin_type: GAS::type_tag(),
+            amount: -10,
+        };
+        let event = Event {
+            package_id: ObjectID::random(),
+            transaction_module: Identifier::new("pool").unwrap(),
+            sender,
+            type_: StructTag::from_str("0x42::pool::Swapped").unwrap(),
+            contents: vec![1, 2, 3],
+        };
+        let response = SuiTransactionBlockResponse {
+            events: Some(SuiTransactionBlockEvents {
+                data: vec![SuiEvent {
+                    id: EventID {
+                        tx_digest: digest,
+                        event_seq: 0,
+                    },
+                    package_id: event.package_id,
+                    transaction_module: event.transaction_module.clone(),
+                    sender,
+                    type_: event.type_.clone(),
+                    parsed_json: serde_json::Value::Null,
+                    bcs: event.contents.clone(),
+                    timestamp_ms: None,
+                }],
+            }),
+            object_changes: Some(vec![object_change.clone()]),
+            balance_changes: Some(vec![balance_change.clone()]),
+            ..SuiTransactionBlockResponse::new(digest)
+        };
+
+        assert_eq!(
+            IndexedTransactionChanges::try_from(&response).unwrap(),
+            IndexedTransactionChanges {
+                object_changes: vec![object_change.into()],
+                balance_change: vec![balance_change],
+                events: vec![event],
+            }
+        );
+
+        let response = SuiTransactionBlockResponse {
+            object_changes: None,
+            ..response
+        };
+        let err = IndexedTransactionChanges::try_from(&response).unwrap_err();
+        assert!(
+            matches!(&err, IndexerError::DataTransformationError(msg) if msg.contains("object changes")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_extract_move_calls() {
+        let package = ObjectID::random();
+        let usd_type = TypeTag::Struct(Box::new(StructTag::from_str("0x42::coin::USD").unwrap()));
+        let sui = GAS::type_tag().to_canonical_string(/* with_prefix */ true);
+        let usd = usd_type.to_canonical_string(/* with_prefix */ true);
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.programmable_move_call(
+            package,
+            Identifier::new("pool").unwrap(),
+            Identifier::new("swap").unwrap(),
+            vec![GAS::type_tag(), usd_type.clone()],
+            vec![],
+        );
+        builder.programmable_move_call(
+            ObjectID::from_single_byte(2),
+            Identifier::new("coin").unwrap(),
+            Identifier::new("zero").unwrap(),
+            vec![usd_type],
+            vec![],
+        );
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        let transaction = TestTransactionBuilder::new(sender, random_object_ref(), 1000)
+            .programmable(builder.finish())
+            .build_and_sign(&keypair);
+
+        assert_eq!(
+            extract_move_calls(transaction.data()),
+            vec![
+                TxMoveCall {
+                    package,
+                    module: "pool".to_string(),
+                    function: "swap".to_string(),
+                    type_arguments: vec![sui, usd.clone()],
+                },
+                TxMoveCall {
+                    package: ObjectID::from_single_byte(2),
+                    module: "coin".to_string(),
+                    function: "zero".to_string(),
+                    type_arguments: vec![usd],
+                },
+            ]
+        );
+
+        let genesis = VerifiedTransaction::new_genesis_transaction(vec![]);
+        assert!(extract_move_calls(genesis.data()).is_empty());
+    }
+
+    #[test]
+    fn test_owner_info_round_trip() {
+        let address = SuiAddress::random_for_testing_only();
+        let owners = [
+            Owner::AddressOwner(address),
+            Owner::ObjectOwner(address),
+            Owner::Shared {
+                initial_shared_version: SequenceNumber::from_u64(7),
+            },
+            Owner::Immutable,
+        ];
+        for owner in owners {
+            let mut object = non_coin_object(ObjectID::random());
+            object.owner = owner;
+            let indexed = IndexedObject::from_object(1, object, None, None).unwrap();
+            assert_eq!(
+                owner_from_info(
+                    indexed.owner_type,
+                    indexed.owner_id,
+                    indexed.owner_initial_shared_version
+                )
+                .unwrap(),
+                owner
+            );
+        }
+
+        assert!(owner_from_info(OwnerType::Address, None, None).is_err());
+        assert!(owner_from_info(OwnerType::Shared, None, None).is_err());
+    }
+
+    #[test]
+    fn test_owner_type_discriminants_are_stable() {
+        // These values are persisted, so they must not change.
+        let owner_types = [
+            (OwnerType::Immutable, 0),
+            (OwnerType::Address, 1),
+            (OwnerType::Object, 2),
+            (OwnerType::Shared, 3),
+        ];
+        for (owner_type, value) in owner_types {
+            assert_eq!(owner_type as i16, value);
+            assert_eq!(OwnerType::try_from(value).unwrap() as i16, value);
+        }
+        assert!(OwnerType::try_from(owner_types.len() as i16).is_err());
+    }
+
+    #[test]
+    fn test_previous_version() {
+        let created = IndexedObject::from_object(1, Object::new_gas_for_testing(), None, None);
+        assert_eq!(created.unwrap().previous_version, None);
+
+        let mut object = Object::new_gas_for_testing();
+        let previous_version = object.version();
+        object
+            .data
+            .try_as_move_mut()
+            .unwrap()
+            .increment_version_to(SequenceNumber::from_u64(previous_version.value() + 1));
+        let mutated =
+            IndexedObject::from_object(1, object, Some(previous_version.value()), None).unwrap();
+        assert_eq!(mutated.previous_version, Some(previous_version.value()));
+        assert_eq!(mutated.object_version, previous_version.value() + 1);
+    }
+
+    #[test]
+    fn test_serialized_object_round_trip() {
+        for object in [
+            Object::new_gas_for_testing(),
+            non_coin_object(ObjectID::random()),
+        ] {
+            let indexed = IndexedObject::from_object(1, object.clone(), None, None).unwrap();
+            let decoded: Object = bcs::from_bytes(&indexed.serialized_object).unwrap();
+            assert_eq!(decoded, object);
+        }
+    }
+}
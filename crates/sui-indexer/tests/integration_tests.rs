@@ -14,12 +14,14 @@ pub mod pg_integration_test {
     use ntest::timeout;
     use std::env;
     use std::str::FromStr;
+    use std::time::Duration;
     use sui_test_transaction_builder::{
         create_devnet_nft, delete_devnet_nft, publish_nfts_package,
     };
     use tokio::task::JoinHandle;
 
     use sui_indexer::errors::IndexerError;
+    use sui_indexer::indexer_reader::IndexerReader;
     use sui_indexer::models::objects::{
         compose_object_bulk_insert_query, compose_object_bulk_insert_update_query,
         filter_latest_objects, NamedBcsBytes, Object, ObjectStatus,
@@ -28,7 +30,10 @@ pub mod pg_integration_test {
     use sui_indexer::schema::objects;
     use sui_indexer::store::{IndexerStore, PgIndexerStore};
     use sui_indexer::test_utils::{start_test_indexer, SuiTransactionBlockResponseBuilder};
-    use sui_indexer::{get_pg_pool_connection, new_pg_connection_pool, IndexerConfig};
+    use sui_indexer::{
+        get_pg_pool_connection, new_pg_connection_pool, new_pg_connection_pool_impl, IndexerConfig,
+        PgConnectionPoolConfig,
+    };
     use sui_json_rpc::api::ExtendedApiClient;
     use sui_json_rpc::api::IndexerApiClient;
     use sui_json_rpc::api::{ReadApiClient, TransactionBuilderClient, WriteApiClient};
@@ -1003,6 +1008,59 @@ pub mod pg_integration_test {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn pg_cancelled_query_releases_connection_test() {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let mut config = PgConnectionPoolConfig::default();
+        config.set_pool_size(2);
+        let reader = IndexerReader::new_with_config(db_url, config).unwrap();
+
+        // Drop the query future long before the query itself would finish.
+        let cancelled = tokio::time::timeout(
+            Duration::from_secs(1),
+            reader.run_cancellable_query(|conn| {
+                diesel::sql_query("SELECT pg_sleep(20)").execute(conn)
+            }),
+        )
+        .await;
+        assert!(cancelled.is_err());
+
+        // Both connections in the pool should be usable again well before the sleep would have
+        // finished.
+        let sleep =
+            || reader.run_query_async(|conn| diesel::sql_query("SELECT pg_sleep(1)").execute(conn));
+        let (first, second) = tokio::time::timeout(
+            Duration::from_secs(10),
+            futures::future::join(sleep(), sleep()),
+        )
+        .await
+        .expect("cancelled query should have released its connection");
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn pg_pool_size_config_test() {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+
+        let pg_connection_pool = new_pg_connection_pool_impl(&db_url, Some(3)).unwrap();
+        assert_eq!(pg_connection_pool.max_size(), 3);
+
+        let mut config = PgConnectionPoolConfig::default();
+        config.set_pool_size(4);
+        config.set_min_idle(1);
+        let reader = IndexerReader::new_with_config(db_url, config).unwrap();
+        assert_eq!(reader.min_idle(), Some(1));
+    }
+
     #[tokio::test]
     #[timeout(60000)]
     async fn pg_unnest_bulk_insert_update_test() {
@@ -588,6 +588,7 @@ impl SuiNode {
             config.expensive_safety_check_config.clone(),
             config.transaction_deny_config.clone(),
             config.certificate_deny_config.clone(),
+            config.transaction_check_config.clone(),
             config.indirect_objects_threshold,
             config.state_debug_dump_config.clone(),
             config.overload_threshold_config.clone(),
@@ -345,6 +345,16 @@ struct FeatureFlags {
     // If true, multisig containing zkLogin sig is accepted.
     #[serde(skip_serializing_if = "is_false")]
     accept_zklogin_in_multisig: bool,
+
+    // If true, each package published or upgraded by a transaction is metered by the bytecode
+    // verifier against its own budget, rather than all of them sharing one.
+    #[serde(skip_serializing_if = "is_false")]
+    per_package_verifier_meter: bool,
+
+    // If true, the transaction input checker rejects transactions that take a shared object
+    // input after the Clock, for flows that replay shared object reads in input order.
+    #[serde(skip_serializing_if = "is_false")]
+    enforce_clock_ordering: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -416,6 +426,19 @@ pub struct ProtocolConfig {
     /// Maximum number of input objects to a transaction. Enforced by the transaction input checker
     max_input_objects: Option<u64>,
 
+    /// Maximum number of shared object inputs to a transaction. Enforced by the transaction input
+    /// checker on top of `max_input_objects`; unlimited when not set.
+    max_shared_object_inputs: Option<u64>,
+
+    /// Maximum number of package inputs to a transaction. Enforced by the transaction input
+    /// checker on top of `max_input_objects`; unlimited when not set.
+    max_package_inputs: Option<u64>,
+
+    /// Maximum number of distinct parents that a transaction may receive objects from. Every
+    /// parent is loaded separately at runtime, so this bounds that work. Enforced by the
+    /// transaction input checker; unlimited when not set.
+    max_receiving_parents: Option<u64>,
+
     /// Max size of objects a transaction can write to disk after completion. Enforce by the Sui adapter.
     /// This is the sum of the serialized size of all objects written to disk.
     /// The max size of individual objects on the other hand is `max_move_object_size`.
@@ -472,6 +495,10 @@ pub struct ProtocolConfig {
     /// Maximum amount of the proposed gas price in MIST (defined in the transaction).
     max_gas_price: Option<u64>,
 
+    /// Maximum gas price, as a multiple of the reference gas price. Enforced wherever the gas
+    /// price is checked, on top of `max_gas_price`; unlimited when not set.
+    max_gas_price_rgp_multiplier: Option<u64>,
+
     /// The max computation bucket for gas. This is the max that can be charged for computation.
     max_gas_computation_bucket: Option<u64>,
 
@@ -1046,6 +1073,14 @@ impl ProtocolConfig {
     pub fn throughput_aware_consensus_submission(&self) -> bool {
         self.feature_flags.throughput_aware_consensus_submission
     }
+
+    pub fn per_package_verifier_meter(&self) -> bool {
+        self.feature_flags.per_package_verifier_meter
+    }
+
+    pub fn enforce_clock_ordering(&self) -> bool {
+        self.feature_flags.enforce_clock_ordering
+    }
 }
 
 #[cfg(not(msim))]
@@ -1161,6 +1196,9 @@ impl ProtocolConfig {
             max_tx_size_bytes: Some(128 * 1024),
             // We need this number to be at least 100x less than `max_serialized_tx_effects_size_bytes`otherwise effects can be huge
             max_input_objects: Some(2048),
+            max_shared_object_inputs: None,
+            max_package_inputs: None,
+            max_receiving_parents: None,
             max_serialized_tx_effects_size_bytes: Some(512 * 1024),
             max_serialized_tx_effects_size_bytes_system_tx: Some(512 * 1024 * 16),
             max_gas_payment_objects: Some(256),
@@ -1176,6 +1214,7 @@ impl ProtocolConfig {
             max_publish_or_upgrade_per_ptb: None,
             max_tx_gas: Some(10_000_000_000),
             max_gas_price: Some(100_000),
+            max_gas_price_rgp_multiplier: None,
             max_gas_computation_bucket: Some(5_000_000),
             max_loop_depth: Some(5),
             max_generic_instantiation_length: Some(32),
@@ -1740,6 +1779,12 @@ impl ProtocolConfig {
     pub fn set_receive_object_for_testing(&mut self, val: bool) {
         self.feature_flags.receive_objects = val
     }
+    pub fn set_per_package_verifier_meter_for_testing(&mut self, val: bool) {
+        self.feature_flags.per_package_verifier_meter = val
+    }
+    pub fn set_enforce_clock_ordering_for_testing(&mut self, val: bool) {
+        self.feature_flags.enforce_clock_ordering = val
+    }
     pub fn set_narwhal_certificate_v2(&mut self, val: bool) {
         self.feature_flags.narwhal_certificate_v2 = val
     }
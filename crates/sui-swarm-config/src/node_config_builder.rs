@@ -158,6 +158,7 @@ impl ValidatorConfigBuilder {
             name_service_reverse_registry_id: None,
             transaction_deny_config: Default::default(),
             certificate_deny_config: Default::default(),
+            transaction_check_config: Default::default(),
             state_debug_dump_config: Default::default(),
             state_archive_write_config: StateArchiveConfig::default(),
             state_archive_read_config: vec![],
@@ -397,6 +398,7 @@ impl FullnodeConfigBuilder {
             name_service_reverse_registry_id: None,
             transaction_deny_config: Default::default(),
             certificate_deny_config: Default::default(),
+            transaction_check_config: Default::default(),
             state_debug_dump_config: Default::default(),
             state_archive_write_config: StateArchiveConfig::default(),
             state_archive_read_config: vec![],
@@ -2,13 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
-use sui_config::transaction_deny_config::TransactionDenyConfig;
+use sui_config::transaction_deny_config::{TransactionDenyConfig, TransactionKindTag};
 use sui_types::{
     base_types::ObjectRef,
     error::{SuiError, SuiResult, UserInputError},
     signature::GenericSignature,
     storage::BackingPackageStore,
-    transaction::{Command, InputObjectKind, TransactionData, TransactionDataAPI},
+    transaction::{
+        Command, InputObjectKind, InputObjects, ReceivingObjects, TransactionData,
+        TransactionDataAPI, TransactionKind,
+    },
 };
 macro_rules! deny_if_true {
     ($cond:expr, $msg:expr) => {
@@ -34,17 +37,55 @@ pub fn check_transaction_for_signing(
 ) -> SuiResult {
     check_disabled_features(filter_config, tx_data, tx_signatures)?;
 
+    check_transaction_kind(filter_config, tx_data)?;
+
     check_signers(filter_config, tx_data)?;
 
     check_input_objects(filter_config, input_object_kinds)?;
 
     check_package_dependencies(filter_config, tx_data, package_store)?;
 
+    check_move_functions(filter_config, tx_data)?;
+
     check_receiving_objects(filter_config, receiving_objects)?;
 
     Ok(())
 }
 
+/// Check that none of the loaded input and receiving objects of a transaction has a type that is
+/// denied by the deny config. Unlike `check_transaction_for_signing`, this needs the objects
+/// themselves, so it has to run after they are loaded.
+pub fn check_object_types_for_signing(
+    input_objects: &InputObjects,
+    receiving_objects: &ReceivingObjects,
+    filter_config: &TransactionDenyConfig,
+) -> SuiResult {
+    let deny_set = filter_config.get_type_deny_set();
+    if deny_set.is_empty() {
+        return Ok(());
+    }
+    let objects = input_objects
+        .iter()
+        .filter_map(|input| input.as_object())
+        .chain(
+            receiving_objects
+                .iter()
+                .filter_map(|receiving| receiving.object.as_object()),
+        );
+    for object in objects {
+        if let Some(type_) = object.struct_tag() {
+            deny_if_true!(
+                deny_set.contains(&type_),
+                format!(
+                    "Access to objects of type {} is temporarily disabled",
+                    type_
+                )
+            );
+        }
+    }
+    Ok(())
+}
+
 fn check_receiving_objects(
     filter_config: &TransactionDenyConfig,
     receiving_objects: &[ObjectRef],
@@ -107,6 +148,39 @@ fn check_disabled_features(
     Ok(())
 }
 
+fn check_transaction_kind(
+    filter_config: &TransactionDenyConfig,
+    tx_data: &TransactionData,
+) -> SuiResult {
+    let denied_kinds = filter_config.denied_transaction_kinds();
+    if denied_kinds.is_empty() {
+        return Ok(());
+    }
+
+    let deny_reason = |kind: TransactionKindTag| {
+        format!("Transactions of kind {:?} are temporarily disabled", kind)
+    };
+    if let TransactionKind::ProgrammableTransaction(_) = tx_data.kind() {
+        deny_if_true!(
+            denied_kinds.contains(&TransactionKindTag::ProgrammableTransaction),
+            deny_reason(TransactionKindTag::ProgrammableTransaction)
+        );
+    }
+    for command in tx_data.kind().iter_commands() {
+        deny_if_true!(
+            denied_kinds.contains(&TransactionKindTag::Publish)
+                && matches!(command, Command::Publish(..)),
+            deny_reason(TransactionKindTag::Publish)
+        );
+        deny_if_true!(
+            denied_kinds.contains(&TransactionKindTag::Upgrade)
+                && matches!(command, Command::Upgrade(..)),
+            deny_reason(TransactionKindTag::Upgrade)
+        );
+    }
+    Ok(())
+}
+
 fn check_signers(filter_config: &TransactionDenyConfig, tx_data: &TransactionData) -> SuiResult {
     let deny_map = filter_config.get_address_deny_set();
     if deny_map.is_empty() {
@@ -148,6 +222,31 @@ fn check_input_objects(
     Ok(())
 }
 
+fn check_move_functions(
+    filter_config: &TransactionDenyConfig,
+    tx_data: &TransactionData,
+) -> SuiResult {
+    if filter_config.get_function_deny_set().is_empty() {
+        return Ok(());
+    }
+    for command in tx_data.kind().iter_commands() {
+        if let Command::MoveCall(call) = command {
+            deny_if_true!(
+                filter_config.is_function_denied(
+                    call.package,
+                    call.module.as_str(),
+                    call.function.as_str()
+                ),
+                format!(
+                    "Calling function {}::{}::{} is temporarily disabled",
+                    call.package, call.module, call.function
+                )
+            );
+        }
+    }
+    Ok(())
+}
+
 fn check_package_dependencies(
     filter_config: &TransactionDenyConfig,
     tx_data: &TransactionData,
@@ -0,0 +1,148 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::error::UserInputError;
+
+/// Stable, machine-readable codes for the classes of `UserInputError` that the transaction checks
+/// produce, so that clients can branch on a failure without parsing its message.
+///
+/// Codes are part of the public interface: once assigned, a code is never renumbered or reused.
+/// New codes are only ever appended.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum CheckErrorCode {
+    /// An error that these checks do not produce themselves.
+    Other = 0,
+
+    // Input objects
+    ObjectNotFound = 1,
+    ObjectVersionUnavailableForConsumption = 2,
+    InvalidObjectDigest = 3,
+    InvalidSequenceNumber = 4,
+    DuplicateObjectRefInput = 5,
+    MutableObjectUsedMoreThanOnce = 6,
+    ObjectInputArity = 7,
+    MoveObjectAsPackage = 8,
+    MovePackageAsObject = 9,
+    InvalidChildObjectArgument = 10,
+    IncorrectUserSignature = 11,
+    NotSharedObject = 12,
+    SharedObjectStartingVersionMismatch = 13,
+    DeletedSharedObjectDigestMismatch = 14,
+    ImmutableParameterExpected = 15,
+    MutableParameterExpected = 16,
+    InconsistentSharedObjectMutability = 17,
+    InaccessibleSystemObject = 18,
+    ClockOrderingViolation = 19,
+    TypeMismatch = 20,
+    UpgradeCapNotFound = 21,
+    MissingPublishDependency = 22,
+
+    // Gas
+    MissingGasPayment = 100,
+    GasCoinsNotFound = 101,
+    InvalidGasObject = 102,
+    GasObjectNotOwnedObject = 103,
+    GasObjectUsedAsSenderInput = 104,
+    GasBudgetTooLow = 105,
+    GasBalanceTooLow = 106,
+    GasPriceExceedsRGPMultiple = 107,
+    GasPriceUnderRGP = 108,
+    GasPriceTooHigh = 109,
+    GasBudgetTooHigh = 110,
+
+    // Limits and packages
+    SizeLimitExceeded = 200,
+    PackageVerificationTimedout = 201,
+
+    // Policy
+    TransactionDenied = 300,
+    Unsupported = 301,
+    MalformedGenesisTransaction = 302,
+}
+
+impl CheckErrorCode {
+    /// The numeric value of this code.
+    pub fn as_u16(self) -> u16 {
+        self as u16
+    }
+}
+
+/// The stable code for `error`. Variants that describe the same failure, such as the two flavours
+/// of a package verification timeout, share a code.
+pub fn code(error: &UserInputError) -> CheckErrorCode {
+    match error {
+        UserInputError::ObjectNotFound { .. } => CheckErrorCode::ObjectNotFound,
+        UserInputError::ObjectVersionUnavailableForConsumption { .. } => {
+            CheckErrorCode::ObjectVersionUnavailableForConsumption
+        }
+        UserInputError::InvalidObjectDigest { .. } => CheckErrorCode::InvalidObjectDigest,
+        UserInputError::InvalidSequenceNumber => CheckErrorCode::InvalidSequenceNumber,
+        UserInputError::DuplicateObjectRefInput => CheckErrorCode::DuplicateObjectRefInput,
+        UserInputError::MutableObjectUsedMoreThanOnce { .. }
+        | UserInputError::MutableObjectUsedMoreThanOnceAt { .. } => {
+            CheckErrorCode::MutableObjectUsedMoreThanOnce
+        }
+        UserInputError::ObjectInputArityViolation => CheckErrorCode::ObjectInputArity,
+        UserInputError::MoveObjectAsPackage { .. } => CheckErrorCode::MoveObjectAsPackage,
+        UserInputError::MovePackageAsObject { .. } => CheckErrorCode::MovePackageAsObject,
+        UserInputError::InvalidChildObjectArgument { .. } => {
+            CheckErrorCode::InvalidChildObjectArgument
+        }
+        UserInputError::IncorrectUserSignature { .. } => CheckErrorCode::IncorrectUserSignature,
+        UserInputError::NotSharedObjectError => CheckErrorCode::NotSharedObject,
+        UserInputError::SharedObjectStartingVersionMismatch => {
+            CheckErrorCode::SharedObjectStartingVersionMismatch
+        }
+        UserInputError::DeletedSharedObjectDigestMismatch { .. } => {
+            CheckErrorCode::DeletedSharedObjectDigestMismatch
+        }
+        UserInputError::ImmutableParameterExpectedError { .. } => {
+            CheckErrorCode::ImmutableParameterExpected
+        }
+        UserInputError::MutableParameterExpected { .. } => CheckErrorCode::MutableParameterExpected,
+        UserInputError::InconsistentSharedObjectMutability { .. } => {
+            CheckErrorCode::InconsistentSharedObjectMutability
+        }
+        UserInputError::InaccessibleSystemObject { .. } => CheckErrorCode::InaccessibleSystemObject,
+        UserInputError::ClockOrderingViolation => CheckErrorCode::ClockOrderingViolation,
+        UserInputError::TypeMismatch { .. } => CheckErrorCode::TypeMismatch,
+        UserInputError::UpgradeCapNotFound { .. } => CheckErrorCode::UpgradeCapNotFound,
+        UserInputError::MissingPublishDependency { .. } => CheckErrorCode::MissingPublishDependency,
+
+        UserInputError::MissingGasPayment => CheckErrorCode::MissingGasPayment,
+        UserInputError::GasCoinsNotFound { .. } => CheckErrorCode::GasCoinsNotFound,
+        UserInputError::InvalidGasObject { .. } => CheckErrorCode::InvalidGasObject,
+        UserInputError::GasObjectNotOwnedObject { .. } => CheckErrorCode::GasObjectNotOwnedObject,
+        UserInputError::GasObjectUsedAsSenderInput { .. } => {
+            CheckErrorCode::GasObjectUsedAsSenderInput
+        }
+        UserInputError::GasBudgetTooLow { .. } => CheckErrorCode::GasBudgetTooLow,
+        UserInputError::GasBalanceTooLow { .. } => CheckErrorCode::GasBalanceTooLow,
+        UserInputError::GasPriceExceedsRGPMultiple { .. } => {
+            CheckErrorCode::GasPriceExceedsRGPMultiple
+        }
+        UserInputError::GasPriceUnderRGP { .. } => CheckErrorCode::GasPriceUnderRGP,
+        UserInputError::GasPriceTooHigh { .. } => CheckErrorCode::GasPriceTooHigh,
+        UserInputError::GasBudgetTooHigh { .. } => CheckErrorCode::GasBudgetTooHigh,
+
+        UserInputError::SizeLimitExceeded { .. } => CheckErrorCode::SizeLimitExceeded,
+        UserInputError::PackageVerificationTimedout { .. }
+        | UserInputError::PackageVerificationTimedoutInCommand { .. } => {
+            CheckErrorCode::PackageVerificationTimedout
+        }
+
+        UserInputError::TransactionDenied { .. } => CheckErrorCode::TransactionDenied,
+        UserInputError::Unsupported(_) => CheckErrorCode::Unsupported,
+        UserInputError::MalformedGenesisTransaction { .. } => {
+            CheckErrorCode::MalformedGenesisTransaction
+        }
+
+        _ => CheckErrorCode::Other,
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/error_code_tests.rs"]
+mod error_code_tests;
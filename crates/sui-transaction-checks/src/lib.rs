@@ -2,36 +2,48 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod deny;
+pub mod error_code;
 
 pub use checked::*;
 
 #[sui_macros::with_checked_arithmetic]
 mod checked {
-    use std::collections::{BTreeMap, HashSet};
+    use move_binary_format::{access::ModuleAccess, CompiledModule};
+    use rayon::prelude::*;
+    use std::collections::hash_map::Entry;
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::sync::atomic::AtomicU64;
     use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use sui_config::transaction_check_config::TransactionCheckConfig;
     use sui_protocol_config::ProtocolConfig;
     use sui_types::base_types::ObjectRef;
+    use sui_types::committee::EpochId;
+    use sui_types::digests::TransactionDigest;
     use sui_types::error::{UserInputError, UserInputResult};
     use sui_types::executable_transaction::VerifiedExecutableTransaction;
-    use sui_types::metrics::BytecodeVerifierMetrics;
+    use sui_types::metrics::{BytecodeVerifierMetrics, CertificateCheckBatchMetrics};
+    use sui_types::storage::{ObjectKey, ObjectStore};
     use sui_types::transaction::{
-        CheckedInputObjects, InputObjectKind, InputObjects, ObjectReadResult, ObjectReadResultKind,
-        ReceivingObjectReadResult, ReceivingObjects, TransactionData, TransactionDataAPI,
+        CheckedInputObjects, Command, GasData, InputObjectKind, InputObjects, ObjectReadResult,
+        ObjectReadResultKind, ProgrammableTransaction, ReceivingObjectReadResult,
+        ReceivingObjectReadResultKind, ReceivingObjects, TransactionData, TransactionDataAPI,
         TransactionKind, VersionedProtocolMessage,
     };
     use sui_types::{
-        base_types::{SequenceNumber, SuiAddress},
+        base_types::{MoveObjectType, ObjectID, SequenceNumber, SuiAddress},
         error::{SuiError, SuiResult},
         fp_bail, fp_ensure,
-        gas::SuiGasStatus,
+        gas::{get_gas_balance, SuiGasStatus},
+        move_package::UpgradeCap,
         object::{Object, Owner},
     };
     use sui_types::{
-        SUI_AUTHENTICATOR_STATE_OBJECT_ID, SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION,
-        SUI_RANDOMNESS_STATE_OBJECT_ID,
+        is_system_package, SUI_AUTHENTICATOR_STATE_OBJECT_ID, SUI_CLOCK_OBJECT_ID,
+        SUI_CLOCK_OBJECT_SHARED_VERSION, SUI_RANDOMNESS_STATE_OBJECT_ID,
     };
-    use tracing::error;
     use tracing::instrument;
+    use tracing::{debug, error, warn};
 
     trait IntoChecked {
         fn into_checked(self) -> CheckedInputObjects;
@@ -43,6 +55,28 @@ mod checked {
         }
     }
 
+    /// How input checks report failures: either return the first one, or collect every failure
+    /// and carry on with the next check.
+    pub(crate) enum InputErrors {
+        FailFast,
+        Collect(Vec<UserInputError>),
+    }
+
+    impl InputErrors {
+        /// Records the outcome of a single check. Only fails when failing fast, so that callers
+        /// can `?` the result and move on to the next check otherwise.
+        fn record(&mut self, result: UserInputResult<()>) -> UserInputResult<()> {
+            match (self, result) {
+                (_, Ok(())) => Ok(()),
+                (InputErrors::FailFast, Err(error)) => Err(error),
+                (InputErrors::Collect(errors), Err(error)) => {
+                    errors.push(error);
+                    Ok(())
+                }
+            }
+        }
+    }
+
     // Entry point for all checks related to gas.
     // Called on both signing and execution.
     // On success the gas part of the transaction (gas data and gas coins)
@@ -65,6 +99,38 @@ mod checked {
         )
     }
 
+    /// An operator-supplied admission policy, run by `check_transaction_input_with_custom_checks`
+    /// once every built-in check has passed.
+    pub trait TransactionCheck: Send + Sync {
+        fn check(&self, tx: &TransactionData, inputs: &InputObjects) -> UserInputResult<()>;
+    }
+
+    /// Rejects programmable transactions with more than `max_move_calls` Move calls.
+    pub struct MaxMoveCallsCheck {
+        pub max_move_calls: usize,
+    }
+
+    impl TransactionCheck for MaxMoveCallsCheck {
+        fn check(&self, tx: &TransactionData, _inputs: &InputObjects) -> UserInputResult<()> {
+            let TransactionKind::ProgrammableTransaction(pt) = tx.kind() else {
+                return Ok(());
+            };
+            let move_calls = pt
+                .commands
+                .iter()
+                .filter(|command| matches!(command, Command::MoveCall(_)))
+                .count();
+            fp_ensure!(
+                move_calls <= self.max_move_calls,
+                UserInputError::SizeLimitExceeded {
+                    limit: "maximum move calls in a programmable transaction".to_string(),
+                    value: self.max_move_calls.to_string(),
+                }
+            );
+            Ok(())
+        }
+    }
+
     #[instrument(level = "trace", skip_all)]
     pub fn check_transaction_input(
         protocol_config: &ProtocolConfig,
@@ -73,25 +139,264 @@ mod checked {
         input_objects: InputObjects,
         receiving_objects: ReceivingObjects,
         metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
     ) -> SuiResult<(SuiGasStatus, CheckedInputObjects)> {
-        transaction.check_version_supported(protocol_config)?;
-        transaction.validity_check(protocol_config)?;
-        // Runs verifier, which could be expensive.
-        check_non_system_packages_to_be_published(transaction, protocol_config, metrics)?;
+        check_transaction_input_with_custom_checks(
+            protocol_config,
+            reference_gas_price,
+            transaction,
+            input_objects,
+            receiving_objects,
+            metrics,
+            check_config,
+            &[],
+        )
+    }
 
-        check_input_objects(&input_objects, protocol_config)?;
-        let gas_status = get_gas_status(
-            &input_objects,
-            transaction.gas(),
+    /// Same as `check_transaction_input`, additionally running `custom_checks` in order after the
+    /// built-in checks have passed.
+    #[instrument(level = "trace", skip_all)]
+    pub fn check_transaction_input_with_custom_checks(
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+        transaction: &TransactionData,
+        input_objects: InputObjects,
+        receiving_objects: ReceivingObjects,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
+        custom_checks: &[Arc<dyn TransactionCheck>],
+    ) -> SuiResult<(SuiGasStatus, CheckedInputObjects)> {
+        check_transaction_input_impl(
             protocol_config,
             reference_gas_price,
             transaction,
+            input_objects,
+            receiving_objects,
+            metrics,
+            check_config,
+            custom_checks,
+            None,
+        )
+    }
+
+    /// Time spent in each phase of `check_transaction_input_with_timings`. Phases that did not
+    /// run, because an earlier one failed, are left at zero.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct CheckTimings {
+        /// Sender, protocol version and validity checks.
+        pub validity: Duration,
+        pub package_verification: Duration,
+        /// Input object limits and sequence numbers.
+        pub input_objects: Duration,
+        pub gas: Duration,
+        pub objects: Duration,
+        pub receiving_objects: Duration,
+        pub custom_checks: Duration,
+    }
+
+    /// Same as `check_transaction_input_with_custom_checks`, also reporting how long each phase
+    /// took, to diagnose slow signing. Timing is only done here, so the other entry points don't
+    /// pay for it.
+    pub fn check_transaction_input_with_timings(
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+        transaction: &TransactionData,
+        input_objects: InputObjects,
+        receiving_objects: ReceivingObjects,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
+        custom_checks: &[Arc<dyn TransactionCheck>],
+    ) -> (SuiResult<(SuiGasStatus, CheckedInputObjects)>, CheckTimings) {
+        let mut timings = CheckTimings::default();
+        let result = check_transaction_input_impl(
+            protocol_config,
+            reference_gas_price,
+            transaction,
+            input_objects,
+            receiving_objects,
+            metrics,
+            check_config,
+            custom_checks,
+            Some(&mut timings),
+        );
+        (result, timings)
+    }
+
+    /// Runs `check`, recording how long it took in `timing` if given.
+    fn timed<T>(timing: Option<&mut Duration>, check: impl FnOnce() -> T) -> T {
+        let Some(timing) = timing else {
+            return check();
+        };
+        let start = Instant::now();
+        let result = check();
+        *timing = start.elapsed();
+        result
+    }
+
+    fn check_transaction_input_impl(
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+        transaction: &TransactionData,
+        input_objects: InputObjects,
+        receiving_objects: ReceivingObjects,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
+        custom_checks: &[Arc<dyn TransactionCheck>],
+        mut timings: Option<&mut CheckTimings>,
+    ) -> SuiResult<(SuiGasStatus, CheckedInputObjects)> {
+        let gas_status = check_transaction_and_gas(
+            protocol_config,
+            reference_gas_price,
+            transaction,
+            &input_objects,
+            &receiving_objects,
+            metrics,
+            check_config,
+            timings.as_deref_mut(),
         )?;
-        check_objects(transaction, &input_objects)?;
-        check_receiving_objects(&input_objects, &receiving_objects)?;
+        timed(timings.as_deref_mut().map(|t| &mut t.objects), || {
+            check_clock_ordering_if_enabled(&input_objects, protocol_config)?;
+            check_objects(transaction, &input_objects)
+        })?;
+        timed(
+            timings.as_deref_mut().map(|t| &mut t.receiving_objects),
+            || check_loaded_receiving_objects(&input_objects, &receiving_objects, protocol_config),
+        )?;
+        timed(timings.as_deref_mut().map(|t| &mut t.custom_checks), || {
+            custom_checks
+                .iter()
+                .try_for_each(|custom_check| custom_check.check(transaction, &input_objects))
+        })?;
         Ok((gas_status, input_objects.into_checked()))
     }
 
+    /// Like `check_transaction_input`, but reports every failing input and receiving object check
+    /// instead of only the first, so that tools building large transactions can surface all
+    /// problems at once. Checks on the transaction as a whole, and gas checks, still stop at the
+    /// first failure. This does more work on invalid transactions than the fail-fast version, so
+    /// it is not meant for the signing path.
+    pub fn check_transaction_input_collecting_errors(
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+        transaction: &TransactionData,
+        input_objects: InputObjects,
+        receiving_objects: ReceivingObjects,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
+    ) -> Result<(SuiGasStatus, CheckedInputObjects), Vec<UserInputError>> {
+        let gas_status = check_transaction_and_gas(
+            protocol_config,
+            reference_gas_price,
+            transaction,
+            &input_objects,
+            &receiving_objects,
+            metrics,
+            check_config,
+            None,
+        )
+        .map_err(|e| vec![into_user_input_error(e)])?;
+
+        let mut errors = InputErrors::Collect(vec![]);
+        // Collecting never fails, but a check can still bail out early if carrying on makes no
+        // sense, in which case its error is reported together with everything collected so far.
+        let result = errors
+            .record(check_clock_ordering_if_enabled(
+                &input_objects,
+                protocol_config,
+            ))
+            .and_then(|()| check_objects_with(transaction, &input_objects, &mut errors))
+            .and_then(|()| {
+                errors.record(check_combined_input_count(
+                    input_objects.len(),
+                    receiving_objects.objects.len(),
+                    protocol_config,
+                ))
+            })
+            .and_then(|()| {
+                check_receiving_objects_with(
+                    &input_objects,
+                    &receiving_objects,
+                    protocol_config,
+                    &mut errors,
+                )
+            });
+
+        let InputErrors::Collect(mut errors) = errors else {
+            unreachable!("errors are being collected");
+        };
+        errors.extend(result.err());
+        if errors.is_empty() {
+            Ok((gas_status, input_objects.into_checked()))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The checks of `check_transaction_input` that come before the per-object checks, and that
+    /// don't make sense to carry on past. Shared by every entry point that checks a transaction's
+    /// own gas payment, so that they all run them in the same order.
+    fn check_transaction_and_gas(
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+        transaction: &TransactionData,
+        input_objects: &InputObjects,
+        receiving_objects: &ReceivingObjects,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
+        mut timings: Option<&mut CheckTimings>,
+    ) -> SuiResult<SuiGasStatus> {
+        timed(
+            timings.as_deref_mut().map(|t| &mut t.validity),
+            || -> SuiResult {
+                check_sender(transaction)?;
+                transaction.check_version_supported(protocol_config)?;
+                transaction.validity_check(protocol_config)?;
+                Ok(())
+            },
+        )?;
+        // Runs verifier, which could be expensive.
+        timed(
+            timings.as_deref_mut().map(|t| &mut t.package_verification),
+            || {
+                check_non_system_packages_to_be_published(
+                    transaction,
+                    protocol_config,
+                    metrics,
+                    check_config,
+                )
+            },
+        )?;
+
+        timed(
+            timings.as_deref_mut().map(|t| &mut t.input_objects),
+            || -> SuiResult {
+                check_input_objects(input_objects, protocol_config)?;
+                check_publish_dependencies(transaction, input_objects, protocol_config)?;
+                let receiving_refs: Vec<_> =
+                    receiving_objects.iter().map(|r| r.object_ref).collect();
+                check_sequence_numbers(input_objects, &receiving_refs)?;
+                Ok(())
+            },
+        )?;
+        timed(timings.as_deref_mut().map(|t| &mut t.gas), || {
+            get_gas_status(
+                input_objects,
+                transaction.gas(),
+                protocol_config,
+                reference_gas_price,
+                transaction,
+            )
+        })
+    }
+
+    /// Unwraps user input errors, and wraps any other error as an unsupported input.
+    fn into_user_input_error(error: SuiError) -> UserInputError {
+        match error {
+            SuiError::UserInputError { error } => error,
+            other => UserInputError::Unsupported(other.to_string()),
+        }
+    }
+
     pub fn check_transaction_input_with_given_gas(
         protocol_config: &ProtocolConfig,
         reference_gas_price: u64,
@@ -100,14 +405,25 @@ mod checked {
         receiving_objects: ReceivingObjects,
         gas_object: Object,
         metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
     ) -> SuiResult<(SuiGasStatus, CheckedInputObjects)> {
+        check_sender(transaction)?;
         transaction.check_version_supported(protocol_config)?;
         transaction.validity_check_no_gas_check(protocol_config)?;
-        check_non_system_packages_to_be_published(transaction, protocol_config, metrics)?;
+        check_non_system_packages_to_be_published(
+            transaction,
+            protocol_config,
+            metrics,
+            check_config,
+        )?;
         check_input_objects(&input_objects, protocol_config)?;
+        check_publish_dependencies(transaction, &input_objects, protocol_config)?;
+        check_given_gas_object(&gas_object)?;
 
         let gas_object_ref = gas_object.compute_object_reference();
         input_objects.push(ObjectReadResult::new_from_gas_object(&gas_object));
+        let receiving_refs: Vec<_> = receiving_objects.iter().map(|r| r.object_ref).collect();
+        check_sequence_numbers(&input_objects, &receiving_refs)?;
 
         let gas_status = get_gas_status(
             &input_objects,
@@ -116,17 +432,130 @@ mod checked {
             reference_gas_price,
             transaction,
         )?;
+        check_clock_ordering_if_enabled(&input_objects, protocol_config)?;
         check_objects(transaction, &input_objects)?;
-        check_receiving_objects(&input_objects, &receiving_objects)?;
+        check_loaded_receiving_objects(&input_objects, &receiving_objects, protocol_config)?;
         Ok((gas_status, input_objects.into_checked()))
     }
 
+    /// Check that the BCS-serialized size of `transaction` does not exceed the protocol's
+    /// `max_tx_size_bytes`. Does not depend on any object state, so clients can run it before
+    /// signing.
+    pub fn check_transaction_size(
+        transaction: &TransactionData,
+        protocol_config: &ProtocolConfig,
+    ) -> UserInputResult<()> {
+        let max_tx_size_bytes = protocol_config.max_tx_size_bytes();
+        let tx_size = bcs::serialized_size(transaction)
+            .expect("Serializing transaction data should not fail") as u64;
+        fp_ensure!(
+            tx_size <= max_tx_size_bytes,
+            UserInputError::SizeLimitExceeded {
+                limit: format!("serialized transaction size of {max_tx_size_bytes} bytes"),
+                value: tx_size.to_string(),
+            }
+        );
+        Ok(())
+    }
+
+    /// The addresses that must sign `transaction`: the sender, followed by the gas owner if the
+    /// transaction is sponsored. A multisig address appears as a single signer here, since which
+    /// of its members sign is only known from the signature itself.
+    pub fn required_signers(transaction: &TransactionData) -> Vec<SuiAddress> {
+        transaction.signers()
+    }
+
+    /// Protocol limits that a transaction can be measured against by `closest_limit`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum LimitKind {
+        InputObjects,
+        ProgrammableTxCommands,
+        TransactionSizeBytes,
+        GasPaymentObjects,
+    }
+
+    /// How much of each relevant protocol limit `transaction` uses, as
+    /// `(kind, used, limit, headroom)`, sorted so that the limit with the least headroom comes
+    /// first. `limit` is the largest value that is still accepted, so the gas payment and command
+    /// limits, which the protocol enforces strictly, are reported one below their configured
+    /// maximum. Unlike the individual checks this never errors, so builders can use it for feedback
+    /// before a transaction is actually rejected.
+    pub fn closest_limit(
+        transaction: &TransactionData,
+        objects: &InputObjects,
+        protocol_config: &ProtocolConfig,
+    ) -> Vec<(LimitKind, u64, u64, u64)> {
+        let tx_size = bcs::serialized_size(transaction)
+            .expect("Serializing transaction data should not fail");
+        let mut usage = vec![
+            (
+                LimitKind::InputObjects,
+                objects.len() as u64,
+                protocol_config.max_input_objects(),
+            ),
+            (
+                LimitKind::ProgrammableTxCommands,
+                transaction.kind().iter_commands().count() as u64,
+                (protocol_config.max_programmable_tx_commands() as u64).saturating_sub(1),
+            ),
+            (
+                LimitKind::TransactionSizeBytes,
+                tx_size as u64,
+                protocol_config.max_tx_size_bytes(),
+            ),
+            (
+                LimitKind::GasPaymentObjects,
+                transaction.gas().len() as u64,
+                (protocol_config.max_gas_payment_objects() as u64).saturating_sub(1),
+            ),
+        ]
+        .into_iter()
+        .map(|(kind, used, limit)| (kind, used, limit, limit.saturating_sub(used)))
+        .collect::<Vec<_>>();
+        usage.sort_by_key(|(_, _, _, headroom)| *headroom);
+        usage
+    }
+
+    /// A gas object supplied by the caller rather than read from the transaction's gas payment
+    /// must still be an address-owned gas coin.
+    pub(crate) fn check_given_gas_object(gas_object: &Object) -> UserInputResult<()> {
+        fp_ensure!(
+            gas_object.is_address_owned(),
+            UserInputError::GasObjectNotOwnedObject {
+                owner: gas_object.owner
+            }
+        );
+        fp_ensure!(
+            gas_object.is_gas_coin(),
+            UserInputError::InvalidGasObject {
+                object_id: gas_object.id()
+            }
+        );
+        Ok(())
+    }
+
     #[instrument(level = "trace", skip_all)]
     pub fn check_certificate_input(
         cert: &VerifiedExecutableTransaction,
         input_objects: InputObjects,
         protocol_config: &ProtocolConfig,
         reference_gas_price: u64,
+    ) -> SuiResult<(SuiGasStatus, CheckedInputObjects)> {
+        check_certificate_input_with_limits(
+            cert,
+            input_objects,
+            protocol_config,
+            reference_gas_price,
+            &InputObjectLimits::new(protocol_config),
+        )
+    }
+
+    fn check_certificate_input_with_limits(
+        cert: &VerifiedExecutableTransaction,
+        input_objects: InputObjects,
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+        input_object_limits: &InputObjectLimits,
     ) -> SuiResult<(SuiGasStatus, CheckedInputObjects)> {
         // This should not happen - validators should not have signed the txn in the first place.
         assert!(
@@ -140,7 +569,8 @@ mod checked {
 
         let tx_data = &cert.data().intent_message().value;
 
-        check_input_objects(&input_objects, protocol_config)?;
+        input_object_limits.check(&input_objects)?;
+        check_sequence_numbers(&input_objects, &[])?;
         let gas_status = get_gas_status(
             &input_objects,
             tx_data.gas(),
@@ -153,8 +583,49 @@ mod checked {
         Ok((gas_status, input_objects.into_checked()))
     }
 
+    /// Runs `check_certificate_input` on each certificate of a batch, e.g. all the transactions of
+    /// a checkpoint read against one store snapshot. Results are in batch order and each one is
+    /// exactly what `check_certificate_input` returns for that certificate. The protocol limits
+    /// are read once for the whole batch.
+    #[instrument(level = "trace", skip_all, fields(batch_size = certs.len()))]
+    pub fn check_certificate_inputs_batch(
+        certs: &[(&VerifiedExecutableTransaction, InputObjects)],
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+        metrics: &CertificateCheckBatchMetrics,
+    ) -> Vec<SuiResult<(SuiGasStatus, CheckedInputObjects)>> {
+        let _timer = metrics.certificate_check_batch_latency.start_timer();
+        metrics
+            .certificate_check_batch_size
+            .observe(certs.len() as f64);
+
+        let input_object_limits = InputObjectLimits::new(protocol_config);
+        let results: Vec<_> = certs
+            .iter()
+            .map(|(cert, input_objects)| {
+                check_certificate_input_with_limits(
+                    cert,
+                    input_objects.clone(),
+                    protocol_config,
+                    reference_gas_price,
+                    &input_object_limits,
+                )
+            })
+            .collect();
+
+        metrics
+            .certificate_check_batch_failures
+            .inc_by(results.iter().filter(|result| result.is_err()).count() as u64);
+        results
+    }
+
     /// WARNING! This should only be used for the dev-inspect transaction. This transaction type
-    /// bypasses many of the normal object checks
+    /// bypasses many of the normal object checks. In particular `check_objects` is not run, so the
+    /// Clock may be taken by mutable reference, which lets simulations advance it.
+    ///
+    /// When `gas_owner` is given, the transaction is treated as sponsored: `gas_object` must be
+    /// owned by `gas_owner`, as it would be for a real sponsored transaction. Without it, the gas
+    /// object's ownership is not checked.
     pub fn check_dev_inspect_input(
         config: &ProtocolConfig,
         kind: &TransactionKind,
@@ -162,9 +633,18 @@ mod checked {
         // TODO: check ReceivingObjects for dev inspect?
         _receiving_objects: ReceivingObjects,
         gas_object: Object,
+        gas_owner: Option<SuiAddress>,
     ) -> SuiResult<(ObjectRef, CheckedInputObjects)> {
         let gas_object_ref = gas_object.compute_object_reference();
         kind.validity_check(config)?;
+        if let Some(gas_owner) = gas_owner {
+            check_one_object(
+                &gas_owner,
+                InputObjectKind::ImmOrOwnedMoveObject(gas_object_ref),
+                &gas_object,
+                false, /* system_transaction */
+            )?;
+        }
         if kind.is_system_tx() {
             return Err(UserInputError::Unsupported(format!(
                 "Transaction kind {} is not supported in dev-inspect",
@@ -173,24 +653,13 @@ mod checked {
             .into());
         }
         check_input_objects(&input_objects, config)?;
-        let mut used_objects: HashSet<SuiAddress> = HashSet::new();
-        for input_object in input_objects.iter() {
-            let Some(object) = input_object.as_object() else {
-                // object was deleted
-                continue;
-            };
-
-            if !object.is_immutable() {
-                fp_ensure!(
-                    used_objects.insert(object.id().into()),
-                    UserInputError::MutableObjectUsedMoreThanOnce {
-                        object_id: object.id()
-                    }
-                    .into()
-                );
-            }
+        let mut used_objects: HashMap<ObjectID, usize> = HashMap::new();
+        for (input_index, object_id) in dev_inspect_mutable_inputs(&input_objects) {
+            record_mutable_use(&mut used_objects, object_id, input_index)?;
         }
 
+        // The gas object is only added after the single-use check above, so it is never counted
+        // against the inputs.
         input_objects.push(ObjectReadResult::new(
             InputObjectKind::ImmOrOwnedMoveObject(gas_object_ref),
             gas_object.into(),
@@ -199,10 +668,259 @@ mod checked {
         Ok((gas_object_ref, input_objects.into_checked()))
     }
 
+    /// The mutable objects among `input_objects` that dev-inspect enforces as single-use, in input
+    /// order. Immutable and deleted objects are skipped.
+    pub fn dev_inspect_mutable_objects(input_objects: &InputObjects) -> Vec<ObjectID> {
+        dev_inspect_mutable_inputs(input_objects)
+            .map(|(_, object_id)| object_id)
+            .collect()
+    }
+
+    /// Like `dev_inspect_mutable_objects`, but paired with each object's input index.
+    fn dev_inspect_mutable_inputs(
+        input_objects: &InputObjects,
+    ) -> impl Iterator<Item = (usize, ObjectID)> + '_ {
+        input_objects
+            .iter()
+            .enumerate()
+            .filter_map(|(input_index, input_object)| {
+                input_object
+                    .as_object()
+                    .filter(|object| !object.is_immutable())
+                    .map(|object| (input_index, object.id()))
+            })
+    }
+
+    /// Check that every shared object is taken with the same mutability wherever it is an input.
+    fn check_shared_object_mutability(objects: &InputObjects) -> UserInputResult<()> {
+        let mut mutability: HashMap<ObjectID, bool> = HashMap::new();
+        for kind in objects.object_kinds() {
+            if let InputObjectKind::SharedMoveObject { id, mutable, .. } = kind {
+                fp_ensure!(
+                    *mutability.entry(*id).or_insert(*mutable) == *mutable,
+                    UserInputError::InconsistentSharedObjectMutability { object_id: *id }
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Records that the mutable object `object_id` is used at `input_index`, failing with both
+    /// input positions if it was already used at an earlier input.
+    fn record_mutable_use(
+        used_objects: &mut HashMap<ObjectID, usize>,
+        object_id: ObjectID,
+        input_index: usize,
+    ) -> UserInputResult<()> {
+        match used_objects.entry(object_id) {
+            Entry::Vacant(entry) => {
+                entry.insert(input_index);
+                Ok(())
+            }
+            Entry::Occupied(entry) => Err(UserInputError::MutableObjectUsedMoreThanOnceAt {
+                object_id,
+                first_input: *entry.get(),
+                second_input: input_index,
+            }),
+        }
+    }
+
+    /// Whether a transaction over `objects` has to be sequenced by consensus, i.e. whether any
+    /// of its inputs is a shared object taken by mutable reference.
+    pub fn requires_consensus(objects: &InputObjects) -> bool {
+        objects
+            .iter()
+            .any(|object| object.is_shared_object() && object.is_mutable())
+    }
+
+    /// Whether any of `objects` is a shared object that has since been deleted.
+    pub fn has_deleted_inputs(objects: &InputObjects) -> bool {
+        objects
+            .iter()
+            .any(|object| matches!(object.object, ObjectReadResultKind::DeletedSharedObject(..)))
+    }
+
+    /// Check that input and receiving objects together stay within `max_input_objects`. The sum
+    /// is computed without wrapping, so that crafted lengths can never overflow past the limit.
+    pub(crate) fn check_combined_input_count(
+        input_count: usize,
+        receiving_count: usize,
+        protocol_config: &ProtocolConfig,
+    ) -> UserInputResult<()> {
+        let max_input_objects = protocol_config.max_input_objects();
+        let within_limit = input_count
+            .checked_add(receiving_count)
+            .and_then(|total| u64::try_from(total).ok())
+            .is_some_and(|total| total <= max_input_objects);
+        fp_ensure!(
+            within_limit,
+            UserInputError::SizeLimitExceeded {
+                limit: "maximum input and receiving objects in a transaction".to_string(),
+                value: max_input_objects.to_string()
+            }
+        );
+        Ok(())
+    }
+
+    /// Check that no input object, nor any object being received, is at `SequenceNumber::MAX`,
+    /// which is reserved and can never be used in a transaction. Owned inputs are checked at the
+    /// version the transaction references, shared inputs at the version that was loaded.
+    pub fn check_sequence_numbers(
+        objects: &InputObjects,
+        receiving: &[ObjectRef],
+    ) -> UserInputResult<()> {
+        let input_versions = objects.iter().filter_map(|object| match &object.object {
+            ObjectReadResultKind::Object(_) if !object.is_shared_object() => object
+                .input_object_kind
+                .version()
+                .map(|version| (object.id(), version)),
+            ObjectReadResultKind::Object(object) => Some((object.id(), object.version())),
+            ObjectReadResultKind::DeletedSharedObject(_, _) => None,
+        });
+        let receiving_versions = receiving
+            .iter()
+            .map(|(object_id, version, _)| (*object_id, *version));
+
+        for (object_id, version) in input_versions.chain(receiving_versions) {
+            if version >= SequenceNumber::MAX {
+                debug!(?object_id, "Input object version is reserved");
+                fp_bail!(UserInputError::InvalidSequenceNumber);
+            }
+        }
+        Ok(())
+    }
+
+    /// Structural checks on a receiving object reference that don't need the object itself: its
+    /// version must be usable, and its ID can't be `0x0`, which no object is ever created at.
+    /// Clients can run this before submitting a transaction.
+    pub fn validate_receiving_ref(object_ref: &ObjectRef) -> UserInputResult<()> {
+        let (object_id, version, _) = *object_ref;
+        fp_ensure!(
+            version < SequenceNumber::MAX,
+            UserInputError::InvalidSequenceNumber
+        );
+        fp_ensure!(
+            object_id != ObjectID::ZERO,
+            UserInputError::ObjectNotFound {
+                object_id,
+                version: Some(version),
+            }
+        );
+        Ok(())
+    }
+
+    /// An object store that also knows which objects were received in an epoch, which is all
+    /// that is needed to load the receiving objects of a transaction.
+    pub trait ReceivingObjectStore: ObjectStore {
+        /// For each key, whether that object has already been received at that version in
+        /// epoch `epoch_id`.
+        fn multi_have_received_object_at_version(
+            &self,
+            object_keys: &[ObjectKey],
+            epoch_id: EpochId,
+        ) -> SuiResult<Vec<bool>>;
+
+        /// The latest version of each object, if it exists.
+        fn multi_get_objects(&self, object_ids: &[ObjectID]) -> SuiResult<Vec<Option<Object>>> {
+            object_ids
+                .iter()
+                .map(|object_id| self.get_object(object_id))
+                .collect()
+        }
+    }
+
+    /// Loads `receiving_objects` from `store`, without checking them. Objects that were already
+    /// received at the requested version are not read at all. The markers and the objects are
+    /// each resolved in one batch, so the number of store calls does not grow with the number of
+    /// receiving objects.
+    pub fn load_receiving_objects<S: ReceivingObjectStore + ?Sized>(
+        store: &S,
+        receiving_objects: &[ObjectRef],
+        epoch_id: EpochId,
+    ) -> SuiResult<ReceivingObjects> {
+        let marker_keys: Vec<_> = receiving_objects.iter().map(ObjectKey::from).collect();
+        let previously_received =
+            store.multi_have_received_object_at_version(&marker_keys, epoch_id)?;
+        assert_eq!(previously_received.len(), receiving_objects.len());
+
+        let fetch_ids: Vec<_> = receiving_objects
+            .iter()
+            .zip(&previously_received)
+            .filter(|(_, received)| !**received)
+            .map(|((object_id, _, _), _)| *object_id)
+            .collect();
+        let mut fetched = store.multi_get_objects(&fetch_ids)?.into_iter();
+
+        let mut loaded = Vec::with_capacity(receiving_objects.len());
+        for (object_ref, received) in receiving_objects.iter().zip(previously_received) {
+            // Note: the digest is checked later, with the other receiving object checks
+            let (object_id, version, _) = object_ref;
+            let object = if received {
+                ReceivingObjectReadResultKind::PreviouslyReceivedObject
+            } else {
+                fetched
+                    .next()
+                    .flatten()
+                    .ok_or(UserInputError::ObjectNotFound {
+                        object_id: *object_id,
+                        version: Some(*version),
+                    })?
+                    .into()
+            };
+            loaded.push(ReceivingObjectReadResult::new(*object_ref, object));
+        }
+        Ok(loaded.into())
+    }
+
+    /// Loads `receiving_objects` from `store` and runs the same receiving object checks as
+    /// `check_transaction_input`, against the given transaction `input_objects`. This lets
+    /// callers such as indexers validate receives without going through the signing path.
+    /// Returns the loaded receiving objects.
+    pub fn validate_receiving_objects<S: ReceivingObjectStore + ?Sized>(
+        store: &S,
+        receiving_objects: &[ObjectRef],
+        input_objects: &InputObjects,
+        protocol_config: &ProtocolConfig,
+        epoch_id: EpochId,
+    ) -> SuiResult<ReceivingObjects> {
+        let loaded = load_receiving_objects(store, receiving_objects, epoch_id)?;
+        check_loaded_receiving_objects(input_objects, &loaded, protocol_config)?;
+        Ok(loaded)
+    }
+
+    fn check_loaded_receiving_objects(
+        input_objects: &InputObjects,
+        receiving_objects: &ReceivingObjects,
+        protocol_config: &ProtocolConfig,
+    ) -> SuiResult {
+        check_combined_input_count(
+            input_objects.len(),
+            receiving_objects.objects.len(),
+            protocol_config,
+        )?;
+        check_receiving_objects(input_objects, receiving_objects, protocol_config)
+    }
+
     fn check_receiving_objects(
         input_objects: &InputObjects,
         receiving_objects: &ReceivingObjects,
+        protocol_config: &ProtocolConfig,
     ) -> Result<(), SuiError> {
+        check_receiving_objects_with(
+            input_objects,
+            receiving_objects,
+            protocol_config,
+            &mut InputErrors::FailFast,
+        )?;
+        Ok(())
+    }
+
+    fn check_receiving_objects_with(
+        input_objects: &InputObjects,
+        receiving_objects: &ReceivingObjects,
+        protocol_config: &ProtocolConfig,
+        errors: &mut InputErrors,
+    ) -> UserInputResult<()> {
         let mut objects_in_txn: HashSet<_> = input_objects
             .object_kinds()
             .map(|x| x.object_id())
@@ -215,112 +933,285 @@ mod checked {
         //
         // If there are any object IDs in common (either between receiving objects and input
         // objects) we return an error.
-        for ReceivingObjectReadResult {
+        for receiving_object in receiving_objects.iter() {
+            errors.record(check_receiving_object(
+                receiving_object,
+                &mut objects_in_txn,
+            ))?;
+        }
+
+        errors.record(check_receiving_parents(
+            receiving_objects,
+            protocol_config.max_receiving_parents_as_option(),
+        ))
+    }
+
+    /// Check one receiving object, and record it in `objects_in_txn` so that later duplicates of
+    /// it are caught.
+    fn check_receiving_object(
+        receiving_object: &ReceivingObjectReadResult,
+        objects_in_txn: &mut HashSet<ObjectID>,
+    ) -> UserInputResult<()> {
+        let ReceivingObjectReadResult {
             object_ref: (object_id, version, object_digest),
             object,
-        } in receiving_objects.iter()
+        } = receiving_object;
+
+        validate_receiving_ref(&(*object_id, *version, *object_digest))?;
+
+        let Some(object) = object.as_object() else {
+            // object was previously received
+            return Ok(());
+        };
+
+        if !(object.owner.is_address_owned()
+            && object.version() == *version
+            && object.digest() == *object_digest)
         {
+            // Version mismatch
             fp_ensure!(
-                *version < SequenceNumber::MAX,
-                UserInputError::InvalidSequenceNumber.into()
+                object.version() == *version,
+                UserInputError::ObjectVersionUnavailableForConsumption {
+                    provided_obj_ref: (*object_id, *version, *object_digest),
+                    current_version: object.version(),
+                }
             );
 
-            let Some(object) = object.as_object() else {
-                // object was previously received
-                continue;
+            // Tried to receive a package
+            fp_ensure!(
+                !object.is_package(),
+                UserInputError::MovePackageAsObject {
+                    object_id: *object_id
+                }
+            );
+
+            // Digest mismatch
+            let expected_digest = object.digest();
+            fp_ensure!(
+                expected_digest == *object_digest,
+                UserInputError::InvalidObjectDigest {
+                    object_id: *object_id,
+                    expected_digest
+                }
+            );
+
+            match object.owner {
+                Owner::AddressOwner(_) => {
+                    debug_assert!(
+                        false,
+                        "Receiving object {:?} is invalid but we expect it should be valid. {:?}",
+                        (*object_id, *version, *object_id),
+                        object
+                    );
+                    error!(
+                        "Receiving object {:?} is invalid but we expect it should be valid. {:?}",
+                        (*object_id, *version, *object_id),
+                        object
+                    );
+                    // We should never get here, but if for some reason we do just default to
+                    // object not found and reject signing the transaction.
+                    fp_bail!(UserInputError::ObjectNotFound {
+                        object_id: *object_id,
+                        version: Some(*version),
+                    })
+                }
+                Owner::ObjectOwner(owner) => {
+                    fp_bail!(UserInputError::InvalidChildObjectArgument {
+                        child_id: object.id(),
+                        parent_id: owner.into(),
+                    })
+                }
+                Owner::Shared { .. } => fp_bail!(UserInputError::NotSharedObjectError),
+                Owner::Immutable => fp_bail!(UserInputError::MutableParameterExpected {
+                    object_id: *object_id
+                }),
             };
+        }
 
-            if !(object.owner.is_address_owned()
-                && object.version() == *version
-                && object.digest() == *object_digest)
-            {
-                // Version mismatch
-                fp_ensure!(
-                    object.version() == *version,
-                    UserInputError::ObjectVersionUnavailableForConsumption {
-                        provided_obj_ref: (*object_id, *version, *object_digest),
-                        current_version: object.version(),
-                    }
-                    .into()
-                );
+        fp_ensure!(
+            !objects_in_txn.contains(object_id),
+            UserInputError::DuplicateObjectRefInput
+        );
 
-                // Tried to receive a package
-                fp_ensure!(
-                    !object.is_package(),
-                    UserInputError::MovePackageAsObject {
-                        object_id: *object_id
-                    }
-                    .into()
-                );
+        objects_in_txn.insert(*object_id);
+        Ok(())
+    }
 
-                // Digest mismatch
-                let expected_digest = object.digest();
-                fp_ensure!(
-                    expected_digest == *object_digest,
-                    UserInputError::InvalidObjectDigest {
-                        object_id: *object_id,
-                        expected_digest
-                    }
-                    .into()
-                );
+    /// Check that `receiving_objects` are owned by at most `max_receiving_parents` distinct
+    /// parents, when the protocol sets that limit. Previously received objects can't be loaded
+    /// and are not counted.
+    pub(crate) fn check_receiving_parents(
+        receiving_objects: &ReceivingObjects,
+        max_receiving_parents: Option<u64>,
+    ) -> UserInputResult<()> {
+        let Some(max_receiving_parents) = max_receiving_parents else {
+            return Ok(());
+        };
+        let parents: HashSet<SuiAddress> = receiving_objects
+            .iter()
+            .filter_map(|receiving| receiving.object.as_object())
+            .filter_map(|object| object.owner.get_owner_address().ok())
+            .collect();
+        fp_ensure!(
+            parents.len() as u64 <= max_receiving_parents,
+            UserInputError::SizeLimitExceeded {
+                limit: "maximum receiving parents in a transaction".to_string(),
+                value: max_receiving_parents.to_string()
+            }
+        );
+        Ok(())
+    }
 
-                match object.owner {
-                    Owner::AddressOwner(_) => {
-                        debug_assert!(false,
-                            "Receiving object {:?} is invalid but we expect it should be valid. {:?}",
-                            (*object_id, *version, *object_id), object
-                        );
-                        error!(
-                            "Receiving object {:?} is invalid but we expect it should be valid. {:?}",
-                            (*object_id, *version, *object_id), object
-                        );
-                        // We should never get here, but if for some reason we do just default to
-                        // object not found and reject signing the transaction.
-                        fp_bail!(UserInputError::ObjectNotFound {
-                            object_id: *object_id,
-                            version: Some(*version),
-                        }
-                        .into())
-                    }
-                    Owner::ObjectOwner(owner) => {
-                        fp_bail!(UserInputError::InvalidChildObjectArgument {
-                            child_id: object.id(),
-                            parent_id: owner.into(),
-                        }
-                        .into())
-                    }
-                    Owner::Shared { .. } => fp_bail!(UserInputError::NotSharedObjectError.into()),
-                    Owner::Immutable => fp_bail!(UserInputError::MutableParameterExpected {
-                        object_id: *object_id
-                    }
-                    .into()),
-                };
+    /// Check that no shared object input comes after the Clock.
+    pub fn check_clock_ordering(objects: &InputObjects) -> UserInputResult<()> {
+        let Some(clock_index) = objects
+            .iter()
+            .position(|object| object.id() == SUI_CLOCK_OBJECT_ID)
+        else {
+            return Ok(());
+        };
+        fp_ensure!(
+            !objects
+                .iter()
+                .skip(clock_index + 1)
+                .any(|object| object.is_shared_object()),
+            UserInputError::ClockOrderingViolation
+        );
+        Ok(())
+    }
+
+    /// Runs `check_clock_ordering` when the protocol enables it. This is only done when signing:
+    /// certificates were already checked by the validators that signed them.
+    pub(crate) fn check_clock_ordering_if_enabled(
+        objects: &InputObjects,
+        protocol_config: &ProtocolConfig,
+    ) -> UserInputResult<()> {
+        if !protocol_config.enforce_clock_ordering() {
+            return Ok(());
+        }
+        check_clock_ordering(objects)
+    }
+
+    pub fn check_input_objects(
+        objects: &InputObjects,
+        protocol_config: &ProtocolConfig,
+    ) -> SuiResult {
+        InputObjectLimits::new(protocol_config).check(objects)
+    }
+
+    /// The protocol limits on the input objects of a transaction, read once so that checking a
+    /// batch of transactions does not look them up again for each one.
+    pub(crate) struct InputObjectLimits {
+        max_input_objects: u64,
+        max_shared_object_inputs: Option<u64>,
+        max_package_inputs: Option<u64>,
+    }
+
+    impl InputObjectLimits {
+        pub(crate) fn new(protocol_config: &ProtocolConfig) -> Self {
+            Self {
+                max_input_objects: protocol_config.max_input_objects(),
+                max_shared_object_inputs: protocol_config.max_shared_object_inputs_as_option(),
+                max_package_inputs: protocol_config.max_package_inputs_as_option(),
             }
+        }
 
+        pub(crate) fn check(&self, objects: &InputObjects) -> SuiResult {
             fp_ensure!(
-                !objects_in_txn.contains(object_id),
-                UserInputError::DuplicateObjectRefInput.into()
+                objects.len() <= self.max_input_objects as usize,
+                UserInputError::SizeLimitExceeded {
+                    limit: "maximum input objects in a transaction".to_string(),
+                    value: self.max_input_objects.to_string()
+                }
+                .into()
             );
+            self.check_kinds(objects)?;
+            Ok(())
+        }
 
-            objects_in_txn.insert(*object_id);
+        /// Enforces the per-kind input limits, for protocol versions that define them.
+        fn check_kinds(&self, objects: &InputObjects) -> UserInputResult<()> {
+            if let Some(max_shared) = self.max_shared_object_inputs {
+                let shared = objects
+                    .iter()
+                    .filter(|o| o.input_object_kind.is_shared_object())
+                    .count();
+                fp_ensure!(
+                    shared as u64 <= max_shared,
+                    UserInputError::SizeLimitExceeded {
+                        limit: "maximum shared object inputs in a transaction".to_string(),
+                        value: max_shared.to_string()
+                    }
+                );
+            }
+            if let Some(max_packages) = self.max_package_inputs {
+                let packages = objects
+                    .iter()
+                    .filter(|o| matches!(o.input_object_kind, InputObjectKind::MovePackage(_)))
+                    .count();
+                fp_ensure!(
+                    packages as u64 <= max_packages,
+                    UserInputError::SizeLimitExceeded {
+                        limit: "maximum package inputs in a transaction".to_string(),
+                        value: max_packages.to_string()
+                    }
+                );
+            }
+            Ok(())
         }
-        Ok(())
     }
 
-    pub fn check_input_objects(
+    /// Whether `objects` is above the operator's `warn_threshold`, logging a warning if so. This
+    /// never rejects anything: it is a capacity planning signal for transactions that are
+    /// trending towards `max_input_objects`, so only call it once they passed the hard limit.
+    pub fn exceeds_input_object_warn_threshold(
         objects: &InputObjects,
         protocol_config: &ProtocolConfig,
-    ) -> SuiResult {
+        warn_threshold: Option<usize>,
+    ) -> bool {
+        match warn_threshold {
+            Some(threshold) if objects.len() > threshold => {
+                warn!(
+                    "Transaction has {} input objects, above the warning threshold of {} (limit {})",
+                    objects.len(),
+                    threshold,
+                    protocol_config.max_input_objects(),
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Check the gas data of a user transaction on its own: a non-empty payment within the limit
+    /// on gas payment objects, a gas price within bounds and a budget within bounds. Gas coins are
+    /// not loaded, so this can run without a store. System transactions are unmetered and their
+    /// gas data is never validated.
+    pub fn validate_gas_data(
+        gas_data: &GasData,
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+    ) -> UserInputResult<()> {
         fp_ensure!(
-            objects.len() <= protocol_config.max_input_objects() as usize,
+            !gas_data.payment.is_empty(),
+            UserInputError::MissingGasPayment
+        );
+        fp_ensure!(
+            gas_data.payment.len() < protocol_config.max_gas_payment_objects() as usize,
             UserInputError::SizeLimitExceeded {
-                limit: "maximum input objects in a transaction".to_string(),
-                value: protocol_config.max_input_objects().to_string()
+                limit: "maximum number of gas payment objects".to_string(),
+                value: protocol_config.max_gas_payment_objects().to_string()
             }
-            .into()
         );
-
-        Ok(())
+        SuiGasStatus::check_gas_price(gas_data.price, reference_gas_price, protocol_config)?;
+        SuiGasStatus::new(
+            gas_data.budget,
+            gas_data.price,
+            reference_gas_price,
+            protocol_config,
+        )
+        .expect("Gas price has already been checked")
+        .check_gas_budget(gas_data.budget)
     }
 
     /// Check transaction gas data/info and gas coins consistency.
@@ -342,37 +1233,156 @@ mod checked {
                 SuiGasStatus::new(gas_budget, gas_price, reference_gas_price, protocol_config)?;
 
             // check balance and coins consistency
-            // load all gas coins
+            // load all gas coins, reporting every missing one before any balance work
             let objects: BTreeMap<_, _> = objects.iter().map(|o| (o.id(), o)).collect();
-            let mut gas_objects = vec![];
-            for obj_ref in gas {
-                let obj = objects.get(&obj_ref.0);
-                let obj = *obj.ok_or(UserInputError::ObjectNotFound {
-                    object_id: obj_ref.0,
-                    version: Some(obj_ref.1),
-                })?;
-                gas_objects.push(obj);
-            }
+            let missing: Vec<ObjectID> = gas
+                .iter()
+                .map(|obj_ref| obj_ref.0)
+                .filter(|object_id| !objects.contains_key(object_id))
+                .collect();
+            fp_ensure!(
+                missing.is_empty(),
+                UserInputError::GasCoinsNotFound { missing }.into()
+            );
+            let gas_objects: Vec<_> = gas.iter().map(|obj_ref| objects[&obj_ref.0]).collect();
             gas_status.check_gas_balance(&gas_objects, gas_budget)?;
             Ok(gas_status)
         }
     }
 
+    /// How much the gas coins of a transaction held, against the budget they had to cover.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct InsufficientGasBalance {
+        pub total_balance: u128,
+        pub required_budget: u128,
+        /// Balance of every gas coin that was summed into `total_balance`, in payment order.
+        pub coin_balances: Vec<(ObjectID, u64)>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum GasCheckError {
+        InsufficientBalance(InsufficientGasBalance),
+        Other(SuiError),
+    }
+
+    impl From<SuiError> for GasCheckError {
+        fn from(error: SuiError) -> Self {
+            Self::Other(error)
+        }
+    }
+
+    /// Same as the gas check run by `check_transaction_input`, for callers such as gas estimation
+    /// tools. When the gas coins cannot cover the budget, the error carries the per-coin
+    /// breakdown rather than just the totals.
+    pub fn check_gas_with_breakdown(
+        objects: &InputObjects,
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+        gas: &[ObjectRef],
+        gas_budget: u64,
+        gas_price: u64,
+        tx_kind: &TransactionKind,
+    ) -> Result<SuiGasStatus, GasCheckError> {
+        match check_gas(
+            objects,
+            protocol_config,
+            reference_gas_price,
+            gas,
+            gas_budget,
+            gas_price,
+            tx_kind,
+        ) {
+            Ok(gas_status) => Ok(gas_status),
+            Err(SuiError::UserInputError {
+                error:
+                    UserInputError::GasBalanceTooLow {
+                        gas_balance,
+                        needed_gas_amount,
+                    },
+            }) => {
+                // check_gas only gets this far once every coin was loaded and is a gas coin.
+                let objects: BTreeMap<_, _> = objects.iter().map(|o| (o.id(), o)).collect();
+                let coin_balances = gas
+                    .iter()
+                    .filter_map(|(object_id, _, _)| objects.get(object_id)?.as_object())
+                    .map(|object| Ok((object.id(), get_gas_balance(object)?)))
+                    .collect::<UserInputResult<_>>()
+                    .map_err(SuiError::from)?;
+                Err(GasCheckError::InsufficientBalance(InsufficientGasBalance {
+                    total_balance: gas_balance,
+                    required_budget: needed_gas_amount,
+                    coin_balances,
+                }))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The smallest gas budget that passes the gas checks for `transaction` paid with
+    /// `gas_coins`. Every budget from the minimum transaction cost up to `max_tx_gas` passes the
+    /// budget check, and a smaller budget only makes the balance check easier, so this is the
+    /// minimum transaction cost whenever the coins can cover it. Otherwise fails with the gas
+    /// check's error for that budget, e.g. `GasBalanceTooLow`.
+    pub fn estimate_min_gas_budget(
+        objects: &InputObjects,
+        gas_coins: &[ObjectRef],
+        protocol_config: &ProtocolConfig,
+        reference_gas_price: u64,
+        transaction: &TransactionData,
+    ) -> SuiResult<u64> {
+        let min_budget = if transaction.is_system_tx() {
+            0
+        } else {
+            SuiGasStatus::new(
+                protocol_config.max_tx_gas(),
+                transaction.gas_price(),
+                reference_gas_price,
+                protocol_config,
+            )?
+            .min_gas_budget()
+        };
+        check_gas(
+            objects,
+            protocol_config,
+            reference_gas_price,
+            gas_coins,
+            min_budget,
+            transaction.gas_price(),
+            transaction.kind(),
+        )?;
+        Ok(min_budget)
+    }
+
     /// Check all the objects used in the transaction against the database, and ensure
     /// that they are all the correct version and number.
     #[instrument(level = "trace", skip_all)]
-    fn check_objects(transaction: &TransactionData, objects: &InputObjects) -> UserInputResult<()> {
+    pub(crate) fn check_objects(
+        transaction: &TransactionData,
+        objects: &InputObjects,
+    ) -> UserInputResult<()> {
+        check_objects_with(transaction, objects, &mut InputErrors::FailFast)
+    }
+
+    pub(crate) fn check_objects_with(
+        transaction: &TransactionData,
+        objects: &InputObjects,
+        errors: &mut InputErrors,
+    ) -> UserInputResult<()> {
+        errors.record(check_sponsored_gas_coins(transaction))?;
+        errors.record(check_genesis_tx_shape(transaction))?;
+
+        errors.record(check_shared_object_mutability(objects))?;
+
         // We require that mutable objects cannot show up more than once.
-        let mut used_objects: HashSet<SuiAddress> = HashSet::new();
+        let mut used_objects: HashMap<ObjectID, usize> = HashMap::new();
         let mut deleted_shared_objects = Vec::new();
-        for object in objects.iter() {
+        for (input_index, object) in objects.iter().enumerate() {
             if object.is_mutable() {
-                fp_ensure!(
-                    used_objects.insert(object.id().into()),
-                    UserInputError::MutableObjectUsedMoreThanOnce {
-                        object_id: object.id()
-                    }
-                );
+                errors.record(record_mutable_use(
+                    &mut used_objects,
+                    object.id(),
+                    input_index,
+                ))?;
             }
         }
 
@@ -380,31 +1390,26 @@ mod checked {
             return Err(UserInputError::ObjectInputArityViolation);
         }
 
+        let gas_ids: HashSet<ObjectID> =
+            transaction.gas().iter().map(|obj_ref| obj_ref.0).collect();
+        let system_transaction = transaction.is_system_tx();
         for object in objects.iter() {
             let input_object_kind = object.input_object_kind;
 
             match &object.object {
                 ObjectReadResultKind::Object(object) => {
-                    // For Gas Object, we check the object is owned by gas owner
-                    // TODO: this is a quadratic check and though limits are low we should do it differently
-                    let owner_address = if transaction
-                        .gas()
-                        .iter()
-                        .any(|obj_ref| *obj_ref.0 == *object.id())
-                    {
-                        transaction.gas_owner()
-                    } else {
-                        transaction.sender()
-                    };
+                    // Immutable objects have no owner to check, and `check_one_object` ignores
+                    // the address for them.
+                    let owner_address = expected_owner_for_input(transaction, &gas_ids, object)
+                        .unwrap_or(SuiAddress::ZERO);
                     // Check if the object contents match the type of lock we need for
                     // this object.
-                    let system_transaction = transaction.is_system_tx();
-                    check_one_object(
+                    errors.record(check_one_object(
                         &owner_address,
                         input_object_kind,
                         object,
                         system_transaction,
-                    )?;
+                    ))?;
                 }
                 // We skip checking a deleted shared object because it no longer exists
                 ObjectReadResultKind::DeletedSharedObject(seq, digest) => {
@@ -421,6 +1426,160 @@ mod checked {
         Ok(())
     }
 
+    /// Same as `check_objects`, but additionally rejects any input listed in `expected_types` whose
+    /// Move type is not the one the transaction's commands expect for it. Inputs missing from the
+    /// map are not type checked.
+    pub fn check_objects_with_expected_types(
+        transaction: &TransactionData,
+        objects: &InputObjects,
+        expected_types: &BTreeMap<ObjectID, MoveObjectType>,
+    ) -> UserInputResult<()> {
+        check_objects(transaction, objects)?;
+        for object in objects.iter() {
+            let ObjectReadResultKind::Object(object) = &object.object else {
+                continue;
+            };
+            if let Some(expected) = expected_types.get(&object.id()) {
+                check_object_type(object, expected)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_object_type(object: &Object, expected: &MoveObjectType) -> UserInputResult<()> {
+        let actual = object.type_();
+        fp_ensure!(
+            actual == Some(expected),
+            UserInputError::TypeMismatch {
+                object_id: object.id(),
+                expected: expected.to_string(),
+                actual: actual.map_or_else(|| "package".to_string(), |t| t.to_string()),
+            }
+        );
+        Ok(())
+    }
+
+    /// Rejects user transactions sent, or sponsored, by the zero address. System transactions are
+    /// sent by the zero address by design and are exempt.
+    pub fn check_sender(transaction: &TransactionData) -> UserInputResult<()> {
+        if transaction.is_system_tx() {
+            return Ok(());
+        }
+        fp_ensure!(
+            transaction.sender() != SuiAddress::ZERO,
+            UserInputError::Unsupported(
+                "transaction sender cannot be the zero address".to_string()
+            )
+        );
+        fp_ensure!(
+            transaction.gas_owner() != SuiAddress::ZERO,
+            UserInputError::Unsupported("gas owner cannot be the zero address".to_string())
+        );
+        Ok(())
+    }
+
+    /// A genesis transaction only carries the placeholder gas payment that every system
+    /// transaction has, and never receives objects.
+    pub fn check_genesis_tx_shape(transaction: &TransactionData) -> UserInputResult<()> {
+        if !transaction.is_genesis_tx() {
+            return Ok(());
+        }
+        fp_ensure!(
+            transaction
+                .gas()
+                .iter()
+                .all(|(object_id, _, _)| *object_id == ObjectID::ZERO),
+            UserInputError::MalformedGenesisTransaction {
+                error: "genesis transaction cannot include a gas payment".to_string()
+            }
+        );
+        fp_ensure!(
+            transaction.receiving_objects().is_empty(),
+            UserInputError::MalformedGenesisTransaction {
+                error: "genesis transaction cannot receive objects".to_string()
+            }
+        );
+        Ok(())
+    }
+
+    /// Check that every deleted shared object in `objects` that has an entry in
+    /// `expected_digests` was deleted by the expected transaction. Deleted shared objects without
+    /// an expectation are not checked, so callers that don't care (i.e. everything except
+    /// replay) can skip this check entirely.
+    pub fn check_deleted_shared_object_digests(
+        objects: &InputObjects,
+        expected_digests: &BTreeMap<ObjectID, TransactionDigest>,
+    ) -> UserInputResult<()> {
+        for object in objects.iter() {
+            let ObjectReadResultKind::DeletedSharedObject(_, actual_digest) = &object.object else {
+                continue;
+            };
+            let object_id = object.id();
+            let Some(expected_digest) = expected_digests.get(&object_id) else {
+                continue;
+            };
+            fp_ensure!(
+                expected_digest == actual_digest,
+                UserInputError::DeletedSharedObjectDigestMismatch {
+                    object_id,
+                    expected_digest: *expected_digest,
+                    actual_digest: *actual_digest,
+                }
+            );
+        }
+        Ok(())
+    }
+
+    /// The address expected to own the input `object_id`: the gas owner for gas coins, and the
+    /// sender for everything else. `gas_ids` holds the ids of the transaction's gas coins.
+    pub(crate) fn expected_owner_for_object(
+        transaction: &TransactionData,
+        gas_ids: &HashSet<ObjectID>,
+        object_id: &ObjectID,
+    ) -> SuiAddress {
+        if gas_ids.contains(object_id) {
+            transaction.gas_owner()
+        } else {
+            transaction.sender()
+        }
+    }
+
+    /// The address expected to own the input `object`, or `None` for immutable objects (including
+    /// packages), which are only checked for their kind and digest, so that working out who
+    /// should own them is skipped.
+    pub(crate) fn expected_owner_for_input(
+        transaction: &TransactionData,
+        gas_ids: &HashSet<ObjectID>,
+        object: &Object,
+    ) -> Option<SuiAddress> {
+        (!object.is_immutable())
+            .then(|| expected_owner_for_object(transaction, gas_ids, &object.id()))
+    }
+
+    /// In a sponsored transaction the gas coins are owned by the sponsor, so none of them may
+    /// also be passed as an input by the sender.
+    fn check_sponsored_gas_coins(transaction: &TransactionData) -> UserInputResult<()> {
+        if !transaction.is_sponsored_tx() {
+            return Ok(());
+        }
+
+        let sender_inputs: HashSet<ObjectID> = transaction
+            .kind()
+            .input_objects()?
+            .iter()
+            .map(|kind| kind.object_id())
+            .collect();
+        for (object_id, _, _) in transaction.gas() {
+            fp_ensure!(
+                !sender_inputs.contains(object_id),
+                UserInputError::GasObjectUsedAsSenderInput {
+                    object_id: *object_id
+                }
+            );
+        }
+        Ok(())
+    }
+
     /// Check one object against a reference
     fn check_one_object(
         owner: &SuiAddress,
@@ -442,11 +1601,6 @@ mod checked {
                     !object.is_package(),
                     UserInputError::MovePackageAsObject { object_id }
                 );
-                fp_ensure!(
-                    sequence_number < SequenceNumber::MAX,
-                    UserInputError::InvalidSequenceNumber
-                );
-
                 // This is an invariant - we just load the object with the given ID and version.
                 assert_eq!(
                 object.version(),
@@ -469,7 +1623,7 @@ mod checked {
 
                 match object.owner {
                     Owner::Immutable => {
-                        // Nothing else to check for Immutable.
+                        // Nothing else to check for Immutable, and `owner` is not meaningful.
                     }
                     Owner::AddressOwner(actual_owner) => {
                         // Check the owner is correct.
@@ -539,11 +1693,6 @@ mod checked {
                 initial_shared_version: input_initial_shared_version,
                 ..
             } => {
-                fp_ensure!(
-                    object.version() < SequenceNumber::MAX,
-                    UserInputError::InvalidSequenceNumber
-                );
-
                 match object.owner {
                     Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
                         // When someone locks an object as shared it must be shared already.
@@ -563,12 +1712,95 @@ mod checked {
         Ok(())
     }
 
+    /// Check that every upgrade command in `transaction` upgrades a package that is among
+    /// `objects`, and that an `UpgradeCap` for that package owned by the sender is among
+    /// `objects` too. Upgrades authorized through a custom policy that wraps the cap don't take
+    /// the cap as an input and are rejected, which is why this is not one of the default checks.
+    pub fn check_upgrade_inputs(
+        transaction: &TransactionData,
+        objects: &InputObjects,
+    ) -> UserInputResult<()> {
+        let TransactionKind::ProgrammableTransaction(pt) = transaction.kind() else {
+            return Ok(());
+        };
+
+        let sender_owner = Owner::AddressOwner(transaction.sender());
+        for command in &pt.commands {
+            let Command::Upgrade(_, _, package_id, _) = command else {
+                continue;
+            };
+            let package_id = *package_id;
+
+            let Some(package) = objects
+                .iter()
+                .find(|object| object.id() == package_id)
+                .and_then(|object| object.as_object())
+            else {
+                fp_bail!(UserInputError::ObjectNotFound {
+                    object_id: package_id,
+                    version: None,
+                });
+            };
+            fp_ensure!(
+                package.is_package(),
+                UserInputError::MoveObjectAsPackage {
+                    object_id: package_id
+                }
+            );
+
+            let has_upgrade_cap = objects
+                .iter()
+                .filter_map(|object| object.as_object())
+                .filter(|object| object.owner == sender_owner)
+                .filter(|object| {
+                    object
+                        .data
+                        .try_as_move()
+                        .is_some_and(|move_object| move_object.is_type(&UpgradeCap::type_()))
+                })
+                .filter_map(|object| object.to_rust::<UpgradeCap>())
+                .any(|cap| cap.package.bytes == package_id);
+            fp_ensure!(
+                has_upgrade_cap,
+                UserInputError::UpgradeCapNotFound { package_id }
+            );
+        }
+        Ok(())
+    }
+
+    /// A lower bound on the gas budget needed to pay for storing the packages published by
+    /// `transaction`, from the total size of their modules and the storage price. This is
+    /// advisory, for wallets to warn on budgets that are bound to fail at execution; it ignores
+    /// computation costs and package metadata. Returns `None` if the transaction publishes
+    /// nothing.
+    pub fn estimate_min_publish_budget(
+        transaction: &TransactionData,
+        protocol_config: &ProtocolConfig,
+    ) -> Option<u64> {
+        let module_bytes = transaction
+            .kind()
+            .iter_commands()
+            .filter_map(|command| match command {
+                Command::Publish(modules, _) => Some(modules),
+                _ => None,
+            })
+            .flatten()
+            .map(|module| module.len() as u64)
+            .reduce(u64::saturating_add)?;
+        Some(
+            module_bytes
+                .saturating_mul(protocol_config.obj_data_cost_refundable())
+                .saturating_mul(protocol_config.storage_gas_price()),
+        )
+    }
+
     /// Check package verification timeout
     #[instrument(level = "trace", skip_all)]
     pub fn check_non_system_packages_to_be_published(
         transaction: &TransactionData,
         protocol_config: &ProtocolConfig,
         metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
     ) -> UserInputResult<()> {
         // Only meter non-system programmable transaction blocks
         if transaction.is_system_tx() {
@@ -579,25 +1811,35 @@ mod checked {
             return Ok(());
         };
 
-        // We use a custom config with metering enabled
-        let is_metered = true;
-        // Use the same verifier and meter for all packages
-        let mut verifier = sui_execution::verifier(protocol_config, is_metered, metrics);
+        check_published_package_sizes(pt, protocol_config)?;
+
+        if protocol_config.per_package_verifier_meter() {
+            return check_packages_with_per_package_meter(
+                pt,
+                protocol_config,
+                metrics,
+                check_config,
+                check_config.parallel_package_verification,
+            );
+        }
 
         // Measure time for verifying all packages in the PTB
         let shared_meter_verifier_timer = metrics
             .verifier_runtime_per_ptb_success_latency
             .start_timer();
 
-        let verifier_status = pt
-            .non_system_packages_to_be_published()
-            .try_for_each(|module_bytes| verifier.meter_module_bytes(protocol_config, module_bytes))
-            .map_err(|e| UserInputError::PackageVerificationTimedout { err: e.to_string() });
+        let verifier_status = if check_config.parallel_package_verification {
+            meter_packages_with_shared_budget(pt, protocol_config, metrics)
+        } else {
+            meter_packages_with_shared_meter(pt, protocol_config, metrics)
+        }
+        .map_err(|e| UserInputError::PackageVerificationTimedout { err: e.to_string() });
 
         match verifier_status {
-            Ok(_) => {
+            Ok(usage_fraction) => {
                 // Success: stop and record the success timer
                 shared_meter_verifier_timer.stop_and_record();
+                record_meter_usage(usage_fraction, metrics, check_config, None);
             }
             Err(err) => {
                 // Failure: redirect the success timers output to the failure timer and
@@ -611,4 +1853,265 @@ mod checked {
 
         Ok(())
     }
+
+    /// Cheap guard run before the verifier: a package whose modules alone exceed
+    /// `max_move_package_size` can never be published, so reject it without paying for metering.
+    /// The authoritative package size check still runs at execution.
+    fn check_published_package_sizes(
+        pt: &ProgrammableTransaction,
+        protocol_config: &ProtocolConfig,
+    ) -> UserInputResult<()> {
+        let max_package_size = protocol_config.max_move_package_size();
+        for modules in pt.non_system_packages_to_be_published() {
+            let package_size = modules
+                .iter()
+                .map(|module| module.len() as u64)
+                .fold(0u64, u64::saturating_add);
+            fp_ensure!(
+                package_size <= max_package_size,
+                UserInputError::SizeLimitExceeded {
+                    limit: "maximum move package size".to_string(),
+                    value: max_package_size.to_string(),
+                }
+            );
+        }
+        Ok(())
+    }
+
+    /// Check that every package that a published or upgraded module depends on is an input of the
+    /// transaction, or is a system package, since execution would otherwise fail to link it.
+    /// Modules refer to their dependencies by original package ID, so an upgraded dependency
+    /// supplied under a later version's ID also counts. Modules that fail to deserialize are left
+    /// for execution to reject.
+    pub fn check_publish_dependencies(
+        transaction: &TransactionData,
+        input_objects: &InputObjects,
+        protocol_config: &ProtocolConfig,
+    ) -> UserInputResult<()> {
+        if transaction.is_system_tx() {
+            return Ok(());
+        }
+        let TransactionKind::ProgrammableTransaction(pt) = transaction.kind() else {
+            return Ok(());
+        };
+
+        let mut supplied = HashSet::new();
+        for package in input_objects
+            .iter()
+            .filter_map(|input| input.as_object()?.data.try_as_package())
+        {
+            supplied.insert(package.id());
+            supplied.insert(package.original_package_id());
+        }
+
+        for module_bytes in pt.non_system_packages_to_be_published().flatten() {
+            let Ok(module) = CompiledModule::deserialize_with_config(
+                module_bytes,
+                protocol_config.move_binary_format_version(),
+                protocol_config.no_extraneous_module_bytes(),
+            ) else {
+                continue;
+            };
+            let self_address = *module.self_id().address();
+            for dependency in module.immediate_dependencies() {
+                let address = *dependency.address();
+                let package_id = ObjectID::from(address);
+                fp_ensure!(
+                    address == self_address
+                        || is_system_package(address)
+                        || supplied.contains(&package_id),
+                    UserInputError::MissingPublishDependency { package_id }
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Meters the packages published or upgraded by `pt` one after the other with a single
+    /// verifier, whose meter they all share. Returns how much of its budget was used.
+    fn meter_packages_with_shared_meter(
+        pt: &ProgrammableTransaction,
+        protocol_config: &ProtocolConfig,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+    ) -> SuiResult<Option<f64>> {
+        // We use a custom config with metering enabled
+        let is_metered = true;
+        // Use the same verifier and meter for all packages
+        let mut verifier = sui_execution::verifier(protocol_config, is_metered, metrics);
+        pt.non_system_packages_to_be_published()
+            .try_for_each(|module_bytes| {
+                verifier.meter_module_bytes(protocol_config, module_bytes)
+            })?;
+        Ok(verifier.meter_usage_fraction())
+    }
+
+    /// Like `meter_packages_with_shared_meter`, but verifies the packages concurrently on the
+    /// rayon thread pool, one verifier per package. The verifiers split a single module budget,
+    /// so the transaction is accepted or rejected exactly as it is by one verifier, and the bound
+    /// on total verification work is unchanged. Falls back to the sequential mode if the
+    /// verifiers of this execution version cannot share a budget.
+    fn meter_packages_with_shared_budget(
+        pt: &ProgrammableTransaction,
+        protocol_config: &ProtocolConfig,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+    ) -> SuiResult<Option<f64>> {
+        let is_metered = true;
+        let shared_module_ticks = Arc::new(AtomicU64::new(0));
+        let packages: Vec<_> = pt.non_system_packages_to_be_published().collect();
+        // The probe verifier meters nothing, so sharing the budget with it is harmless
+        if packages.len() < 2
+            || !sui_execution::verifier(protocol_config, is_metered, metrics)
+                .share_module_budget(shared_module_ticks.clone())
+        {
+            return meter_packages_with_shared_meter(pt, protocol_config, metrics);
+        }
+
+        let results: Vec<SuiResult<Option<f64>>> = packages
+            .into_par_iter()
+            .map(|module_bytes| {
+                let mut verifier = sui_execution::verifier(protocol_config, is_metered, metrics);
+                verifier.share_module_budget(shared_module_ticks.clone());
+                verifier.meter_module_bytes(protocol_config, module_bytes)?;
+                Ok(verifier.meter_usage_fraction())
+            })
+            .collect();
+
+        // Results are in command order, so an error is the earliest timeout
+        results
+            .into_iter()
+            .try_fold(None, |peak: Option<f64>, usage_fraction| {
+                Ok([peak, usage_fraction?]
+                    .into_iter()
+                    .flatten()
+                    .reduce(f64::max))
+            })
+    }
+
+    /// Meters each package published or upgraded by `pt` against a budget of its own, so that one
+    /// large package cannot use up the budget of the others, and reports which command timed out.
+    ///
+    /// With `parallel`, the packages are verified concurrently on the rayon thread pool. Every
+    /// package still gets exactly one budget, so the bound on verification work is the same as in
+    /// the sequential mode, and the per-PTB latency metrics record the wall-clock time of the
+    /// whole batch. If several packages time out, the earliest command is reported either way.
+    pub fn check_packages_with_per_package_meter(
+        pt: &ProgrammableTransaction,
+        protocol_config: &ProtocolConfig,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
+        parallel: bool,
+    ) -> UserInputResult<()> {
+        let packages: Vec<_> = pt
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(command_index, command)| match command {
+                Command::Publish(module_bytes, _) | Command::Upgrade(module_bytes, _, _, _) => {
+                    Some((command_index, module_bytes))
+                }
+                _ => None,
+            })
+            .collect();
+
+        if !parallel {
+            return packages
+                .into_iter()
+                .try_for_each(|(command_index, module_bytes)| {
+                    meter_package(
+                        command_index,
+                        module_bytes,
+                        protocol_config,
+                        metrics,
+                        check_config,
+                    )
+                });
+        }
+
+        let ptb_verifier_timer = metrics
+            .verifier_runtime_per_ptb_success_latency
+            .start_timer();
+        let results: Vec<_> = packages
+            .into_par_iter()
+            .map(|(command_index, module_bytes)| {
+                meter_package(
+                    command_index,
+                    module_bytes,
+                    protocol_config,
+                    metrics,
+                    check_config,
+                )
+            })
+            .collect();
+
+        // Results are in command order, so this is the earliest timeout
+        match results.into_iter().collect::<UserInputResult<()>>() {
+            Ok(()) => {
+                ptb_verifier_timer.stop_and_record();
+                Ok(())
+            }
+            Err(err) => {
+                metrics
+                    .verifier_runtime_per_ptb_timeout_latency
+                    .observe(ptb_verifier_timer.stop_and_discard());
+                Err(err)
+            }
+        }
+    }
+
+    fn meter_package(
+        command_index: usize,
+        module_bytes: &[Vec<u8>],
+        protocol_config: &ProtocolConfig,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+        check_config: &TransactionCheckConfig,
+    ) -> UserInputResult<()> {
+        let is_metered = true;
+        // A fresh verifier comes with a fresh meter
+        let mut verifier = sui_execution::verifier(protocol_config, is_metered, metrics);
+        let package_verifier_timer = metrics
+            .verifier_runtime_per_package_success_latency
+            .start_timer();
+        if let Err(e) = verifier.meter_module_bytes(protocol_config, module_bytes) {
+            metrics
+                .verifier_runtime_per_package_timeout_latency
+                .observe(package_verifier_timer.stop_and_discard());
+            return Err(UserInputError::PackageVerificationTimedoutInCommand {
+                command_index,
+                err: e.to_string(),
+            });
+        }
+        package_verifier_timer.stop_and_record();
+        record_meter_usage(
+            verifier.meter_usage_fraction(),
+            metrics,
+            check_config,
+            Some(command_index),
+        );
+        Ok(())
+    }
+
+    /// Records how close a successful verification came to the meter limit, so that limits can be
+    /// tuned before legitimate packages start timing out.
+    fn record_meter_usage(
+        usage_fraction: Option<f64>,
+        metrics: &BytecodeVerifierMetrics,
+        check_config: &TransactionCheckConfig,
+        command_index: Option<usize>,
+    ) {
+        let Some(fraction) = usage_fraction else {
+            return;
+        };
+        metrics.verifier_meter_usage_fraction.observe(fraction);
+
+        if fraction >= check_config.verifier_meter_warn_fraction() {
+            warn!(
+                ?command_index,
+                fraction, "Package verification came close to the verifier meter limit"
+            );
+        }
+    }
 }
+
+#[cfg(test)]
+#[path = "unit_tests/checks_tests.rs"]
+mod checks_tests;
@@ -0,0 +1,1619 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::checked::*;
+use move_binary_format::file_format::{
+    empty_module, AddressIdentifierIndex, IdentifierIndex, ModuleHandle,
+};
+use move_binary_format::CompiledModule;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use sui_config::transaction_check_config::TransactionCheckConfig;
+use sui_protocol_config::ProtocolConfig;
+use sui_types::base_types::{
+    random_object_ref, MoveObjectType, ObjectDigest, ObjectID, ObjectRef, SequenceNumber,
+    SuiAddress,
+};
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use sui_types::digests::TransactionDigest;
+use sui_types::error::{SuiError, SuiResult, UserInputError};
+use sui_types::executable_transaction::VerifiedExecutableTransaction;
+use sui_types::metrics::{BytecodeVerifierMetrics, CertificateCheckBatchMetrics};
+use sui_types::move_package::{MovePackage, UpgradeCap};
+use sui_types::object::{MoveObject, Object, Owner};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::storage::{ObjectKey, ObjectStore};
+use sui_types::transaction::{
+    Argument, Command, GasData, GenesisTransaction, InputObjectKind, InputObjects,
+    ObjectReadResult, ObjectReadResultKind, ProgrammableTransaction, ReceivingObjectReadResult,
+    ReceivingObjectReadResultKind, ReceivingObjects, TransactionData, TransactionDataAPI,
+    TransactionKind, VerifiedTransaction,
+};
+use sui_types::utils::to_sender_signed_transaction;
+use sui_types::{
+    Identifier, TypeTag, SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION,
+    SUI_FRAMEWORK_PACKAGE_ID,
+};
+
+const TEST_GAS_BUDGET: u64 = 50_000_000;
+const TEST_GAS_PRICE: u64 = 1_000;
+
+fn owned_input(object: &Object) -> ObjectReadResult {
+    ObjectReadResult::new(
+        InputObjectKind::ImmOrOwnedMoveObject(object.compute_object_reference()),
+        object.clone().into(),
+    )
+}
+
+fn transfer_objects_tx(
+    sender: SuiAddress,
+    objects: Vec<ObjectRef>,
+    gas: Vec<ObjectRef>,
+    sponsor: SuiAddress,
+) -> TransactionData {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    for object in objects {
+        builder
+            .transfer_object(SuiAddress::random_for_testing_only(), object)
+            .unwrap();
+    }
+    TransactionData::new_programmable_allow_sponsor(
+        sender,
+        gas,
+        builder.finish(),
+        TEST_GAS_BUDGET,
+        TEST_GAS_PRICE,
+        sponsor,
+    )
+}
+
+#[test]
+fn test_sponsored_gas_coin_used_as_sender_input() {
+    let sender = SuiAddress::random_for_testing_only();
+    let sponsor = SuiAddress::random_for_testing_only();
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sponsor);
+    let gas_ref = gas.compute_object_reference();
+
+    let transaction = transfer_objects_tx(sender, vec![gas_ref], vec![gas_ref], sponsor);
+    let objects = InputObjects::new(vec![owned_input(&gas), owned_input(&gas)]);
+
+    assert_eq!(
+        check_objects(&transaction, &objects).unwrap_err(),
+        UserInputError::GasObjectUsedAsSenderInput {
+            object_id: gas.id()
+        }
+    );
+}
+
+#[test]
+fn test_mutable_object_used_twice_reports_both_inputs() {
+    let sender = SuiAddress::random_for_testing_only();
+    let owned = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let immutable = Object::immutable_with_id_for_testing(ObjectID::random());
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+
+    let transaction = transfer_objects_tx(
+        sender,
+        vec![owned.compute_object_reference()],
+        vec![gas.compute_object_reference()],
+        sender,
+    );
+    let objects = InputObjects::new(vec![
+        owned_input(&owned),
+        owned_input(&immutable),
+        owned_input(&owned),
+    ]);
+
+    assert_eq!(
+        check_objects(&transaction, &objects).unwrap_err(),
+        UserInputError::MutableObjectUsedMoreThanOnceAt {
+            object_id: owned.id(),
+            first_input: 0,
+            second_input: 2,
+        }
+    );
+}
+
+#[test]
+fn test_transaction_size_limit() {
+    let sender = SuiAddress::random_for_testing_only();
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let transaction = transfer_objects_tx(
+        sender,
+        vec![
+            Object::with_id_owner_for_testing(ObjectID::random(), sender)
+                .compute_object_reference(),
+        ],
+        vec![gas.compute_object_reference()],
+        sender,
+    );
+    let tx_size = bcs::serialized_size(&transaction).unwrap() as u64;
+
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    protocol_config.set_max_tx_size_bytes_for_testing(tx_size);
+    assert!(check_transaction_size(&transaction, &protocol_config).is_ok());
+
+    protocol_config.set_max_tx_size_bytes_for_testing(tx_size - 1);
+    assert_eq!(
+        check_transaction_size(&transaction, &protocol_config).unwrap_err(),
+        UserInputError::SizeLimitExceeded {
+            limit: format!("serialized transaction size of {} bytes", tx_size - 1),
+            value: tx_size.to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_dev_inspect_mutable_objects() {
+    let sender = SuiAddress::random_for_testing_only();
+    let immutable = Object::immutable_with_id_for_testing(ObjectID::random());
+    let owned = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let shared = Object::shared_for_testing();
+    let input_objects = InputObjects::new(vec![
+        owned_input(&immutable),
+        owned_input(&owned),
+        ObjectReadResult::new(
+            InputObjectKind::SharedMoveObject {
+                id: shared.id(),
+                initial_shared_version: shared.version(),
+                mutable: true,
+            },
+            shared.clone().into(),
+        ),
+    ]);
+
+    assert_eq!(
+        dev_inspect_mutable_objects(&input_objects),
+        vec![owned.id(), shared.id()]
+    );
+}
+
+#[test]
+fn test_dev_inspect_gas_not_counted_as_input() {
+    let sender = SuiAddress::random_for_testing_only();
+    let owned = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let immutable = Object::immutable_with_id_for_testing(ObjectID::random());
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let transaction = transfer_objects_tx(
+        sender,
+        vec![owned.compute_object_reference()],
+        vec![gas.compute_object_reference()],
+        sender,
+    );
+    let input_objects = InputObjects::new(vec![owned_input(&owned), owned_input(&immutable)]);
+
+    let (gas_ref, checked) = check_dev_inspect_input(
+        &ProtocolConfig::get_for_max_version_UNSAFE(),
+        transaction.kind(),
+        input_objects,
+        ReceivingObjects::from(vec![]),
+        gas.clone(),
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(gas_ref, gas.compute_object_reference());
+    assert_eq!(
+        dev_inspect_mutable_objects(checked.inner()),
+        vec![owned.id(), gas.id()]
+    );
+}
+
+#[test]
+fn test_dev_inspect_sponsored_gas() {
+    let sender = SuiAddress::random_for_testing_only();
+    let sponsor = SuiAddress::random_for_testing_only();
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sponsor);
+    let transaction =
+        transfer_objects_tx(sender, vec![], vec![gas.compute_object_reference()], sender);
+    let check = |gas_owner| {
+        check_dev_inspect_input(
+            &ProtocolConfig::get_for_max_version_UNSAFE(),
+            transaction.kind(),
+            InputObjects::new(vec![]),
+            ReceivingObjects::from(vec![]),
+            gas.clone(),
+            gas_owner,
+        )
+        .map(|(gas_ref, _)| gas_ref)
+    };
+
+    // Without a gas owner, ownership of the gas object is not checked, as before.
+    assert_eq!(check(None).unwrap(), gas.compute_object_reference());
+    assert_eq!(
+        check(Some(sponsor)).unwrap(),
+        gas.compute_object_reference()
+    );
+    assert!(matches!(
+        check(Some(sender)).unwrap_err(),
+        SuiError::UserInputError {
+            error: UserInputError::IncorrectUserSignature { .. }
+        }
+    ));
+}
+
+#[test]
+fn test_mutable_clock_only_in_dev_inspect() {
+    let sender = SuiAddress::random_for_testing_only();
+    let gas = Object::with_owner_for_testing(sender);
+    // The contents of the clock don't matter to these checks.
+    let clock = Object::new_move(
+        MoveObject::new_gas_coin(SUI_CLOCK_OBJECT_SHARED_VERSION, SUI_CLOCK_OBJECT_ID, 0),
+        Owner::Shared {
+            initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+        },
+        TransactionDigest::genesis(),
+    );
+    let mutable_clock = ObjectReadResult::new(
+        InputObjectKind::SharedMoveObject {
+            id: SUI_CLOCK_OBJECT_ID,
+            initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+            mutable: true,
+        },
+        clock.into(),
+    );
+    let transaction =
+        transfer_objects_tx(sender, vec![], vec![gas.compute_object_reference()], sender);
+
+    check_dev_inspect_input(
+        &ProtocolConfig::get_for_max_version_UNSAFE(),
+        transaction.kind(),
+        InputObjects::new(vec![mutable_clock.clone()]),
+        ReceivingObjects::from(vec![]),
+        gas.clone(),
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        check_objects(
+            &transaction,
+            &InputObjects::new(vec![mutable_clock, owned_input(&gas)])
+        )
+        .unwrap_err(),
+        UserInputError::ImmutableParameterExpectedError {
+            object_id: SUI_CLOCK_OBJECT_ID
+        }
+    );
+}
+
+#[test]
+fn test_given_gas_object_must_be_owned() {
+    let shared = Object::shared_for_testing();
+    assert_eq!(
+        check_given_gas_object(&shared).unwrap_err(),
+        UserInputError::GasObjectNotOwnedObject {
+            owner: shared.owner
+        }
+    );
+
+    let immutable = Object::immutable_with_id_for_testing(ObjectID::random());
+    assert_eq!(
+        check_given_gas_object(&immutable).unwrap_err(),
+        UserInputError::GasObjectNotOwnedObject {
+            owner: immutable.owner
+        }
+    );
+
+    // Address-owned, but not a gas coin.
+    let cap = upgrade_cap(SuiAddress::ZERO, ObjectID::random());
+    assert_eq!(
+        check_given_gas_object(&cap).unwrap_err(),
+        UserInputError::InvalidGasObject {
+            object_id: cap.id()
+        }
+    );
+
+    let owned = Object::with_id_owner_for_testing(ObjectID::random(), SuiAddress::ZERO);
+    assert!(check_given_gas_object(&owned).is_ok());
+}
+
+#[test]
+fn test_required_signers() {
+    let sender = SuiAddress::random_for_testing_only();
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let transaction =
+        transfer_objects_tx(sender, vec![], vec![gas.compute_object_reference()], sender);
+    assert_eq!(required_signers(&transaction), vec![sender]);
+
+    let sponsor = SuiAddress::random_for_testing_only();
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sponsor);
+    let transaction = transfer_objects_tx(
+        sender,
+        vec![],
+        vec![gas.compute_object_reference()],
+        sponsor,
+    );
+    assert_eq!(required_signers(&transaction), vec![sender, sponsor]);
+}
+
+#[test]
+fn test_input_object_warn_threshold() {
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    protocol_config.set_max_input_objects_for_testing(4);
+    let objects = |count| {
+        InputObjects::new(
+            (0..count)
+                .map(|_| {
+                    owned_input(&Object::with_id_owner_for_testing(
+                        ObjectID::random(),
+                        SuiAddress::ZERO,
+                    ))
+                })
+                .collect(),
+        )
+    };
+
+    // Between the warning threshold and the hard limit: accepted, but flagged.
+    check_input_objects(&objects(3), &protocol_config).unwrap();
+    assert!(exceeds_input_object_warn_threshold(
+        &objects(3),
+        &protocol_config,
+        Some(2)
+    ));
+    // At or below the warning threshold: no warning.
+    assert!(!exceeds_input_object_warn_threshold(
+        &objects(2),
+        &protocol_config,
+        Some(2)
+    ));
+    assert!(!exceeds_input_object_warn_threshold(
+        &objects(3),
+        &protocol_config,
+        None
+    ));
+    // Over the hard limit: rejected regardless of the warning threshold.
+    assert!(check_input_objects(&objects(5), &protocol_config).is_err());
+}
+
+#[test]
+fn test_combined_input_count() {
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    protocol_config.set_max_input_objects_for_testing(10);
+
+    assert!(check_combined_input_count(6, 4, &protocol_config).is_ok());
+    assert!(check_combined_input_count(6, 5, &protocol_config).is_err());
+    // Lengths whose sum would wrap around must be rejected rather than wrap to a small count.
+    assert!(check_combined_input_count(usize::MAX, 2, &protocol_config).is_err());
+    assert!(check_combined_input_count(2, usize::MAX, &protocol_config).is_err());
+}
+
+#[test]
+fn test_genesis_tx_shape() {
+    let genesis = VerifiedTransaction::new_genesis_transaction(vec![]);
+    assert!(check_genesis_tx_shape(genesis.data().transaction_data()).is_ok());
+
+    let sender = SuiAddress::random_for_testing_only();
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let malformed = TransactionData::new(
+        TransactionKind::Genesis(GenesisTransaction { objects: vec![] }),
+        sender,
+        gas.compute_object_reference(),
+        TEST_GAS_BUDGET,
+        TEST_GAS_PRICE,
+    );
+    assert!(matches!(
+        check_genesis_tx_shape(&malformed).unwrap_err(),
+        UserInputError::MalformedGenesisTransaction { .. }
+    ));
+}
+
+#[test]
+fn test_closest_limit() {
+    let sender = SuiAddress::random_for_testing_only();
+    let objects = (0..4)
+        .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), sender))
+        .collect::<Vec<_>>();
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let transaction = transfer_objects_tx(
+        sender,
+        objects
+            .iter()
+            .map(|o| o.compute_object_reference())
+            .collect(),
+        vec![gas.compute_object_reference()],
+        sender,
+    );
+    let input_objects = InputObjects::new(objects.iter().map(owned_input).collect());
+
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    protocol_config.set_max_input_objects_for_testing(5);
+
+    let usage = closest_limit(&transaction, &input_objects, &protocol_config);
+    assert_eq!(usage[0], (LimitKind::InputObjects, 4, 5, 1));
+    assert_eq!(usage.len(), 4);
+    assert!(usage.windows(2).all(|w| w[0].3 <= w[1].3));
+
+    // The gas payment limit is strict, so with a maximum of two only a single gas coin fits and
+    // there is no headroom left.
+    protocol_config.set_max_gas_payment_objects_for_testing(2);
+    let usage = closest_limit(&transaction, &input_objects, &protocol_config);
+    assert_eq!(usage[0], (LimitKind::GasPaymentObjects, 1, 1, 0));
+    assert!(transaction.validity_check(&protocol_config).is_ok());
+
+    // One below that, the single gas coin is already over the limit.
+    protocol_config.set_max_gas_payment_objects_for_testing(1);
+    let usage = closest_limit(&transaction, &input_objects, &protocol_config);
+    assert_eq!(usage[0], (LimitKind::GasPaymentObjects, 1, 0, 0));
+    assert!(transaction.validity_check(&protocol_config).is_err());
+}
+
+#[test]
+fn test_expected_owner_for_object() {
+    let sender = SuiAddress::random_for_testing_only();
+    let sponsor = SuiAddress::random_for_testing_only();
+    let object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sponsor);
+    let transaction = transfer_objects_tx(
+        sender,
+        vec![object.compute_object_reference()],
+        vec![gas.compute_object_reference()],
+        sponsor,
+    );
+
+    let gas_ids = HashSet::from([gas.id()]);
+    assert_eq!(
+        expected_owner_for_object(&transaction, &gas_ids, &gas.id()),
+        sponsor
+    );
+    assert_eq!(
+        expected_owner_for_object(&transaction, &gas_ids, &object.id()),
+        sender
+    );
+}
+
+#[test]
+fn test_check_objects_with_many_gas_coins() {
+    let sender = SuiAddress::random_for_testing_only();
+    let sponsor = SuiAddress::random_for_testing_only();
+    let objects: Vec<_> = (0..4)
+        .map(|_| Object::with_owner_for_testing(sender))
+        .collect();
+    let gas: Vec<_> = (0..4)
+        .map(|_| Object::with_owner_for_testing(sponsor))
+        .collect();
+    let refs = |objects: &[Object]| {
+        objects
+            .iter()
+            .map(|o| o.compute_object_reference())
+            .collect()
+    };
+    let transaction = transfer_objects_tx(sender, refs(&objects), refs(&gas), sponsor);
+    let inputs = InputObjects::new(objects.iter().chain(&gas).map(owned_input).collect());
+
+    // Gas coins are checked against the sponsor and everything else against the sender.
+    check_objects(&transaction, &inputs).unwrap();
+
+    // A sender-owned object sneaked in as gas is checked against the sponsor.
+    let mut stolen_gas = gas.clone();
+    stolen_gas[2] = objects[0].clone();
+    let transaction = transfer_objects_tx(sender, refs(&objects[1..]), refs(&stolen_gas), sponsor);
+    let inputs = InputObjects::new(
+        objects[1..]
+            .iter()
+            .chain(&stolen_gas)
+            .map(owned_input)
+            .collect(),
+    );
+    assert!(matches!(
+        check_objects(&transaction, &inputs).unwrap_err(),
+        UserInputError::IncorrectUserSignature { .. }
+    ));
+}
+
+#[test]
+fn test_deleted_shared_object_digests() {
+    let object_id = ObjectID::random();
+    let deleted_by = TransactionDigest::random();
+    let objects = InputObjects::new(vec![ObjectReadResult::new(
+        InputObjectKind::SharedMoveObject {
+            id: object_id,
+            initial_shared_version: 1.into(),
+            mutable: true,
+        },
+        ObjectReadResultKind::DeletedSharedObject(5.into(), deleted_by),
+    )]);
+
+    // Nothing expected, nothing checked.
+    assert!(check_deleted_shared_object_digests(&objects, &BTreeMap::new()).is_ok());
+    assert!(check_deleted_shared_object_digests(
+        &objects,
+        &BTreeMap::from([(object_id, deleted_by)])
+    )
+    .is_ok());
+
+    let expected = TransactionDigest::random();
+    assert_eq!(
+        check_deleted_shared_object_digests(&objects, &BTreeMap::from([(object_id, expected)]))
+            .unwrap_err(),
+        UserInputError::DeletedSharedObjectDigestMismatch {
+            object_id,
+            expected_digest: expected,
+            actual_digest: deleted_by,
+        }
+    );
+}
+
+#[test]
+fn test_requires_consensus() {
+    let shared = Object::shared_for_testing();
+    let shared_input = |mutable| {
+        ObjectReadResult::new(
+            InputObjectKind::SharedMoveObject {
+                id: shared.id(),
+                initial_shared_version: shared.version(),
+                mutable,
+            },
+            shared.clone().into(),
+        )
+    };
+    let owned = Object::with_owner_for_testing(SuiAddress::random_for_testing_only());
+
+    assert!(requires_consensus(&InputObjects::new(vec![
+        owned_input(&owned),
+        shared_input(true),
+    ])));
+    assert!(!requires_consensus(&InputObjects::new(vec![
+        owned_input(&owned),
+        shared_input(false),
+    ])));
+    assert!(!requires_consensus(&InputObjects::new(vec![owned_input(
+        &owned
+    )])));
+}
+
+#[test]
+fn test_has_deleted_inputs() {
+    let shared = Object::shared_for_testing();
+    let kind = InputObjectKind::SharedMoveObject {
+        id: shared.id(),
+        initial_shared_version: shared.version(),
+        mutable: true,
+    };
+    let owned = Object::with_owner_for_testing(SuiAddress::random_for_testing_only());
+
+    assert!(!has_deleted_inputs(&InputObjects::new(vec![
+        owned_input(&owned),
+        ObjectReadResult::new(kind, shared.clone().into()),
+    ])));
+    assert!(has_deleted_inputs(&InputObjects::new(vec![
+        owned_input(&owned),
+        ObjectReadResult {
+            input_object_kind: kind,
+            object: ObjectReadResultKind::DeletedSharedObject(
+                shared.version().next(),
+                TransactionDigest::random(),
+            ),
+        },
+    ])));
+}
+
+#[test]
+fn test_sequence_numbers() {
+    let object = Object::with_owner_for_testing(SuiAddress::random_for_testing_only());
+    let objects = InputObjects::new(vec![owned_input(&object)]);
+    assert!(check_sequence_numbers(&objects, &[]).is_ok());
+
+    let (object_id, _, digest) = object.compute_object_reference();
+    let max_version_input = InputObjects::new(vec![ObjectReadResult::new(
+        InputObjectKind::ImmOrOwnedMoveObject((object_id, SequenceNumber::MAX, digest)),
+        object.clone().into(),
+    )]);
+    assert_eq!(
+        check_sequence_numbers(&max_version_input, &[]).unwrap_err(),
+        UserInputError::InvalidSequenceNumber
+    );
+
+    let max_version_receiving = (ObjectID::random(), SequenceNumber::MAX, digest);
+    assert_eq!(
+        check_sequence_numbers(&objects, &[max_version_receiving]).unwrap_err(),
+        UserInputError::InvalidSequenceNumber
+    );
+}
+
+#[test]
+fn test_validate_receiving_ref() {
+    let object = Object::with_owner_for_testing(SuiAddress::random_for_testing_only());
+    let (object_id, version, digest) = object.compute_object_reference();
+    assert!(validate_receiving_ref(&(object_id, version, digest)).is_ok());
+
+    assert_eq!(
+        validate_receiving_ref(&(object_id, SequenceNumber::MAX, digest)).unwrap_err(),
+        UserInputError::InvalidSequenceNumber
+    );
+    assert_eq!(
+        validate_receiving_ref(&(ObjectID::ZERO, version, digest)).unwrap_err(),
+        UserInputError::ObjectNotFound {
+            object_id: ObjectID::ZERO,
+            version: Some(version),
+        }
+    );
+}
+
+#[test]
+fn test_receiving_parents_limit() {
+    let parents = [
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+    ];
+    let receiving = |object: Object| {
+        ReceivingObjectReadResult::new(
+            object.compute_object_reference(),
+            ReceivingObjectReadResultKind::Object(object.into()),
+        )
+    };
+    // Two objects from the first parent, one from the second.
+    let receiving_objects = ReceivingObjects::from(vec![
+        receiving(Object::with_owner_for_testing(parents[0])),
+        receiving(Object::with_owner_for_testing(parents[0])),
+        receiving(Object::with_owner_for_testing(parents[1])),
+    ]);
+
+    assert!(check_receiving_parents(&receiving_objects, None).is_ok());
+    assert!(check_receiving_parents(&receiving_objects, Some(2)).is_ok());
+    assert!(matches!(
+        check_receiving_parents(&receiving_objects, Some(1)).unwrap_err(),
+        UserInputError::SizeLimitExceeded { .. }
+    ));
+}
+
+#[test]
+fn test_validate_gas_data() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let owner = SuiAddress::random_for_testing_only();
+    let gas = Object::with_owner_for_testing(owner);
+    let gas_data = GasData {
+        payment: vec![gas.compute_object_reference()],
+        owner,
+        price: TEST_GAS_PRICE,
+        budget: TEST_GAS_BUDGET,
+    };
+    assert!(validate_gas_data(&gas_data, &protocol_config, TEST_GAS_PRICE).is_ok());
+
+    let no_payment = GasData {
+        payment: vec![],
+        ..gas_data.clone()
+    };
+    assert_eq!(
+        validate_gas_data(&no_payment, &protocol_config, TEST_GAS_PRICE).unwrap_err(),
+        UserInputError::MissingGasPayment
+    );
+
+    assert_eq!(
+        validate_gas_data(&gas_data, &protocol_config, TEST_GAS_PRICE + 1).unwrap_err(),
+        UserInputError::GasPriceUnderRGP {
+            gas_price: TEST_GAS_PRICE,
+            reference_gas_price: TEST_GAS_PRICE + 1,
+        }
+    );
+}
+
+fn upgrade_cap(owner: SuiAddress, package_id: ObjectID) -> Object {
+    let cap = UpgradeCap::new(ObjectID::random(), package_id);
+    let data = unsafe {
+        MoveObject::new_from_execution_with_limit(
+            UpgradeCap::type_().into(),
+            true,
+            1.into(),
+            bcs::to_bytes(&cap).unwrap(),
+            256,
+        )
+    }
+    .unwrap();
+    Object::new_move(
+        data,
+        Owner::AddressOwner(owner),
+        TransactionDigest::genesis(),
+    )
+}
+
+#[test]
+fn test_upgrade_inputs() {
+    let sender = SuiAddress::random_for_testing_only();
+    let package_id = ObjectID::random();
+    let package = Object::new_from_package(
+        MovePackage::new(
+            package_id,
+            1.into(),
+            BTreeMap::new(),
+            u64::MAX,
+            vec![],
+            BTreeMap::new(),
+        )
+        .unwrap(),
+        TransactionDigest::genesis(),
+    );
+    let package_input =
+        ObjectReadResult::new(InputObjectKind::MovePackage(package_id), package.into());
+    let gas = Object::with_owner_for_testing(sender);
+    let transaction = TransactionData::new_programmable(
+        sender,
+        vec![gas.compute_object_reference()],
+        ProgrammableTransaction {
+            inputs: vec![],
+            commands: vec![Command::Upgrade(
+                vec![vec![]],
+                vec![],
+                package_id,
+                Argument::Result(0),
+            )],
+        },
+        TEST_GAS_BUDGET,
+        TEST_GAS_PRICE,
+    );
+
+    let with_cap = InputObjects::new(vec![
+        package_input.clone(),
+        owned_input(&upgrade_cap(sender, package_id)),
+    ]);
+    assert!(check_upgrade_inputs(&transaction, &with_cap).is_ok());
+
+    let without_cap = InputObjects::new(vec![package_input.clone()]);
+    assert_eq!(
+        check_upgrade_inputs(&transaction, &without_cap).unwrap_err(),
+        UserInputError::UpgradeCapNotFound { package_id }
+    );
+
+    // A cap owned by someone else, or for another package, doesn't count.
+    let others_cap = InputObjects::new(vec![
+        package_input.clone(),
+        owned_input(&upgrade_cap(
+            SuiAddress::random_for_testing_only(),
+            package_id,
+        )),
+        owned_input(&upgrade_cap(sender, ObjectID::random())),
+    ]);
+    assert_eq!(
+        check_upgrade_inputs(&transaction, &others_cap).unwrap_err(),
+        UserInputError::UpgradeCapNotFound { package_id }
+    );
+
+    let without_package = InputObjects::new(vec![owned_input(&upgrade_cap(sender, package_id))]);
+    assert_eq!(
+        check_upgrade_inputs(&transaction, &without_package).unwrap_err(),
+        UserInputError::ObjectNotFound {
+            object_id: package_id,
+            version: None,
+        }
+    );
+}
+
+#[test]
+fn test_check_objects_collects_errors() {
+    let sender = SuiAddress::random_for_testing_only();
+    let objects: Vec<_> = (0..3)
+        .map(|_| Object::with_owner_for_testing(sender))
+        .collect();
+    let gas = Object::with_owner_for_testing(sender);
+    let transaction = transfer_objects_tx(
+        sender,
+        objects
+            .iter()
+            .map(|o| o.compute_object_reference())
+            .collect(),
+        vec![gas.compute_object_reference()],
+        sender,
+    );
+    // The first and last inputs reference the wrong digest.
+    let input = |object: &Object, valid: bool| {
+        let (object_id, version, digest) = object.compute_object_reference();
+        let digest = if valid {
+            digest
+        } else {
+            ObjectDigest::random()
+        };
+        ObjectReadResult::new(
+            InputObjectKind::ImmOrOwnedMoveObject((object_id, version, digest)),
+            object.clone().into(),
+        )
+    };
+    let inputs = InputObjects::new(vec![
+        input(&objects[0], false),
+        input(&objects[1], true),
+        input(&objects[2], false),
+        owned_input(&gas),
+    ]);
+
+    assert!(matches!(
+        check_objects(&transaction, &inputs).unwrap_err(),
+        UserInputError::InvalidObjectDigest { object_id, .. } if object_id == objects[0].id()
+    ));
+
+    let mut errors = InputErrors::Collect(vec![]);
+    check_objects_with(&transaction, &inputs, &mut errors).unwrap();
+    let InputErrors::Collect(errors) = errors else {
+        unreachable!();
+    };
+    let failed: Vec<_> = errors
+        .iter()
+        .map(|error| match error {
+            UserInputError::InvalidObjectDigest { object_id, .. } => *object_id,
+            other => panic!("unexpected error: {other:?}"),
+        })
+        .collect();
+    assert_eq!(failed, vec![objects[0].id(), objects[2].id()]);
+}
+
+#[test]
+fn test_check_objects_with_expected_types() {
+    let sender = SuiAddress::random_for_testing_only();
+    let object = Object::with_owner_for_testing(sender);
+    let gas = Object::with_owner_for_testing(sender);
+    let transaction = transfer_objects_tx(
+        sender,
+        vec![object.compute_object_reference()],
+        vec![gas.compute_object_reference()],
+        sender,
+    );
+    let inputs = InputObjects::new(vec![owned_input(&object), owned_input(&gas)]);
+
+    // No expectations, or a matching one, passes.
+    check_objects_with_expected_types(&transaction, &inputs, &BTreeMap::new()).unwrap();
+    let matching = BTreeMap::from([(object.id(), MoveObjectType::gas_coin())]);
+    check_objects_with_expected_types(&transaction, &inputs, &matching).unwrap();
+
+    // A gas coin passed where a StakedSui is expected is rejected.
+    let mismatched = BTreeMap::from([(object.id(), MoveObjectType::staked_sui())]);
+    let err = check_objects_with_expected_types(&transaction, &inputs, &mismatched).unwrap_err();
+    assert_eq!(
+        err,
+        UserInputError::TypeMismatch {
+            object_id: object.id(),
+            expected: MoveObjectType::staked_sui().to_string(),
+            actual: MoveObjectType::gas_coin().to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_check_gas_with_breakdown() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let owner = SuiAddress::random_for_testing_only();
+    let coins: Vec<_> = [100_000_000, 200_000_000, 300_000_000]
+        .into_iter()
+        .map(|value| Object::new_gas_with_balance_and_owner_for_testing(value, owner))
+        .collect();
+    let inputs = InputObjects::new(coins.iter().map(owned_input).collect());
+    let gas: Vec<_> = coins.iter().map(|c| c.compute_object_reference()).collect();
+    let tx_kind =
+        TransactionKind::ProgrammableTransaction(ProgrammableTransactionBuilder::new().finish());
+    let check = |budget| {
+        check_gas_with_breakdown(
+            &inputs,
+            &protocol_config,
+            TEST_GAS_PRICE,
+            &gas,
+            budget,
+            TEST_GAS_PRICE,
+            &tx_kind,
+        )
+    };
+
+    assert!(check(600_000_000).is_ok());
+    assert_eq!(
+        check(700_000_000).unwrap_err(),
+        GasCheckError::InsufficientBalance(InsufficientGasBalance {
+            total_balance: 600_000_000,
+            required_budget: 700_000_000,
+            coin_balances: coins
+                .iter()
+                .zip([100_000_000, 200_000_000, 300_000_000])
+                .map(|(coin, value)| (coin.id(), value))
+                .collect(),
+        })
+    );
+}
+
+#[test]
+fn test_check_gas_reports_all_missing_coins() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let owner = SuiAddress::random_for_testing_only();
+    let present = Object::new_gas_with_balance_and_owner_for_testing(100_000_000, owner);
+    let missing = [random_object_ref(), random_object_ref()];
+    let inputs = InputObjects::new(vec![owned_input(&present)]);
+    let gas = vec![missing[0], present.compute_object_reference(), missing[1]];
+    let tx_kind =
+        TransactionKind::ProgrammableTransaction(ProgrammableTransactionBuilder::new().finish());
+
+    assert_eq!(
+        check_gas_with_breakdown(
+            &inputs,
+            &protocol_config,
+            TEST_GAS_PRICE,
+            &gas,
+            TEST_GAS_BUDGET,
+            TEST_GAS_PRICE,
+            &tx_kind,
+        )
+        .unwrap_err(),
+        GasCheckError::Other(
+            UserInputError::GasCoinsNotFound {
+                missing: vec![missing[0].0, missing[1].0],
+            }
+            .into()
+        )
+    );
+}
+
+#[test]
+fn test_gas_price_rgp_multiplier() {
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let owner = SuiAddress::random_for_testing_only();
+    let coin = Object::new_gas_with_balance_and_owner_for_testing(100_000_000_000, owner);
+    let inputs = InputObjects::new(vec![owned_input(&coin)]);
+    let gas = vec![coin.compute_object_reference()];
+    let tx_kind =
+        TransactionKind::ProgrammableTransaction(ProgrammableTransactionBuilder::new().finish());
+    let check = |protocol_config: &ProtocolConfig, gas_price| {
+        check_gas_with_breakdown(
+            &inputs,
+            protocol_config,
+            TEST_GAS_PRICE,
+            &gas,
+            TEST_GAS_BUDGET,
+            gas_price,
+            &tx_kind,
+        )
+    };
+
+    // Unlimited unless configured.
+    assert!(check(&protocol_config, 50 * TEST_GAS_PRICE).is_ok());
+
+    protocol_config.set_max_gas_price_rgp_multiplier_for_testing(10);
+    assert!(check(&protocol_config, 10 * TEST_GAS_PRICE).is_ok());
+    assert_eq!(
+        check(&protocol_config, 10 * TEST_GAS_PRICE + 1).unwrap_err(),
+        GasCheckError::Other(
+            UserInputError::GasPriceExceedsRGPMultiple {
+                gas_price: 10 * TEST_GAS_PRICE + 1,
+                max_allowed: 10 * TEST_GAS_PRICE,
+            }
+            .into()
+        )
+    );
+
+    // The gas data check applies the same cap.
+    let gas_data = GasData {
+        payment: gas.clone(),
+        owner,
+        price: 10 * TEST_GAS_PRICE + 1,
+        budget: TEST_GAS_BUDGET,
+    };
+    assert_eq!(
+        validate_gas_data(&gas_data, &protocol_config, TEST_GAS_PRICE).unwrap_err(),
+        UserInputError::GasPriceExceedsRGPMultiple {
+            gas_price: 10 * TEST_GAS_PRICE + 1,
+            max_allowed: 10 * TEST_GAS_PRICE,
+        }
+    );
+}
+
+#[test]
+fn test_check_sender() {
+    let sender = SuiAddress::random_for_testing_only();
+    let object = Object::with_owner_for_testing(sender);
+    let gas = Object::with_owner_for_testing(sender);
+    let tx = |sender, sponsor| {
+        transfer_objects_tx(
+            sender,
+            vec![object.compute_object_reference()],
+            vec![gas.compute_object_reference()],
+            sponsor,
+        )
+    };
+
+    check_sender(&tx(sender, sender)).unwrap();
+    assert!(matches!(
+        check_sender(&tx(SuiAddress::ZERO, SuiAddress::ZERO)).unwrap_err(),
+        UserInputError::Unsupported(_)
+    ));
+    assert!(matches!(
+        check_sender(&tx(sender, SuiAddress::ZERO)).unwrap_err(),
+        UserInputError::Unsupported(_)
+    ));
+
+    let genesis = VerifiedTransaction::new_genesis_transaction(vec![]);
+    check_sender(genesis.data().transaction_data()).unwrap();
+}
+
+#[test]
+fn test_input_object_kind_limits() {
+    let shared_input = |shared: &Object| {
+        ObjectReadResult::new(
+            InputObjectKind::SharedMoveObject {
+                id: shared.id(),
+                initial_shared_version: shared.version(),
+                mutable: true,
+            },
+            shared.clone().into(),
+        )
+    };
+    let shared: Vec<_> = (0..3).map(|_| Object::shared_for_testing()).collect();
+    let owned = Object::with_owner_for_testing(SuiAddress::random_for_testing_only());
+    let objects = InputObjects::new(
+        shared
+            .iter()
+            .map(shared_input)
+            .chain([owned_input(&owned)])
+            .collect(),
+    );
+
+    // Unset limits leave only the total count in place.
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    assert!(protocol_config
+        .max_shared_object_inputs_as_option()
+        .is_none());
+    assert!(protocol_config.max_package_inputs_as_option().is_none());
+    check_input_objects(&objects, &protocol_config).unwrap();
+
+    protocol_config.set_max_shared_object_inputs_for_testing(3);
+    protocol_config.set_max_package_inputs_for_testing(0);
+    check_input_objects(&objects, &protocol_config).unwrap();
+
+    protocol_config.set_max_shared_object_inputs_for_testing(2);
+    assert_eq!(
+        check_input_objects(&objects, &protocol_config).unwrap_err(),
+        UserInputError::SizeLimitExceeded {
+            limit: "maximum shared object inputs in a transaction".to_string(),
+            value: "2".to_string(),
+        }
+        .into()
+    );
+}
+
+#[test]
+fn test_type_argument_limits() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let max = protocol_config.max_type_arguments() as usize;
+    let move_call_tx = |type_args: usize| {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.programmable_move_call(
+            ObjectID::random(),
+            Identifier::new("m").unwrap(),
+            Identifier::new("f").unwrap(),
+            vec![],
+            vec![],
+        );
+        builder.programmable_move_call(
+            ObjectID::random(),
+            Identifier::new("m").unwrap(),
+            Identifier::new("f").unwrap(),
+            vec![TypeTag::U64; type_args],
+            vec![],
+        );
+        TransactionData::new_programmable(
+            SuiAddress::random_for_testing_only(),
+            vec![random_object_ref()],
+            builder.finish(),
+            TEST_GAS_BUDGET,
+            TEST_GAS_PRICE,
+        )
+    };
+
+    // The bound is enforced by each Move call's validity check, which every signing path runs
+    // before the input checks.
+    move_call_tx(max - 1)
+        .validity_check_no_gas_check(&protocol_config)
+        .unwrap();
+    assert_eq!(
+        move_call_tx(max)
+            .validity_check_no_gas_check(&protocol_config)
+            .unwrap_err(),
+        UserInputError::SizeLimitExceeded {
+            limit: "maximum type arguments in a call transaction".to_string(),
+            value: max.to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_max_move_calls_check() {
+    let move_calls_tx = |move_calls: usize| {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        for _ in 0..move_calls {
+            builder.programmable_move_call(
+                ObjectID::random(),
+                Identifier::new("m").unwrap(),
+                Identifier::new("f").unwrap(),
+                vec![],
+                vec![],
+            );
+        }
+        TransactionData::new_programmable(
+            SuiAddress::random_for_testing_only(),
+            vec![random_object_ref()],
+            builder.finish(),
+            TEST_GAS_BUDGET,
+            TEST_GAS_PRICE,
+        )
+    };
+    let check: Arc<dyn TransactionCheck> = Arc::new(MaxMoveCallsCheck { max_move_calls: 2 });
+    let inputs = InputObjects::new(vec![]);
+
+    check.check(&move_calls_tx(2), &inputs).unwrap();
+    assert_eq!(
+        check.check(&move_calls_tx(3), &inputs).unwrap_err(),
+        UserInputError::SizeLimitExceeded {
+            limit: "maximum move calls in a programmable transaction".to_string(),
+            value: "2".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_estimate_min_publish_budget() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let tx = |builder: ProgrammableTransactionBuilder| {
+        TransactionData::new_programmable(
+            SuiAddress::random_for_testing_only(),
+            vec![random_object_ref()],
+            builder.finish(),
+            TEST_GAS_BUDGET,
+            TEST_GAS_PRICE,
+        )
+    };
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.publish_immutable(vec![vec![0; 1_000], vec![0; 500]], vec![]);
+    assert_eq!(
+        estimate_min_publish_budget(&tx(builder), &protocol_config),
+        Some(
+            1_500
+                * protocol_config.obj_data_cost_refundable()
+                * protocol_config.storage_gas_price()
+        )
+    );
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder
+        .transfer_object(SuiAddress::random_for_testing_only(), random_object_ref())
+        .unwrap();
+    assert_eq!(
+        estimate_min_publish_budget(&tx(builder), &protocol_config),
+        None
+    );
+}
+
+#[test]
+fn test_check_clock_ordering() {
+    let shared_input = |object: Object| {
+        ObjectReadResult::new(
+            InputObjectKind::SharedMoveObject {
+                id: object.id(),
+                initial_shared_version: object.version(),
+                mutable: false,
+            },
+            object.into(),
+        )
+    };
+    // The contents of the clock don't matter to this check.
+    let clock = shared_input(Object::new_move(
+        MoveObject::new_gas_coin(SUI_CLOCK_OBJECT_SHARED_VERSION, SUI_CLOCK_OBJECT_ID, 0),
+        Owner::Shared {
+            initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+        },
+        TransactionDigest::genesis(),
+    ));
+    let shared = shared_input(Object::shared_for_testing());
+    let owned = owned_input(&Object::with_owner_for_testing(
+        SuiAddress::random_for_testing_only(),
+    ));
+
+    check_clock_ordering(&InputObjects::new(vec![shared.clone(), owned.clone()])).unwrap();
+    check_clock_ordering(&InputObjects::new(vec![
+        shared.clone(),
+        clock.clone(),
+        owned.clone(),
+    ]))
+    .unwrap();
+    let out_of_order = InputObjects::new(vec![clock, shared, owned]);
+    assert_eq!(
+        check_clock_ordering(&out_of_order).unwrap_err(),
+        UserInputError::ClockOrderingViolation
+    );
+
+    // Only enforced when the protocol enables it.
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    check_clock_ordering_if_enabled(&out_of_order, &protocol_config).unwrap();
+    protocol_config.set_enforce_clock_ordering_for_testing(true);
+    assert_eq!(
+        check_clock_ordering_if_enabled(&out_of_order, &protocol_config).unwrap_err(),
+        UserInputError::ClockOrderingViolation
+    );
+}
+
+#[derive(Default)]
+struct MockReceivingStore {
+    objects: HashMap<ObjectID, Object>,
+    received: HashSet<(ObjectID, SequenceNumber)>,
+}
+
+impl ObjectStore for MockReceivingStore {
+    fn get_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        Ok(self.objects.get(object_id).cloned())
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        Ok(self
+            .objects
+            .get(object_id)
+            .filter(|object| object.version() == version)
+            .cloned())
+    }
+}
+
+impl ReceivingObjectStore for MockReceivingStore {
+    fn multi_have_received_object_at_version(
+        &self,
+        object_keys: &[ObjectKey],
+        _epoch_id: u64,
+    ) -> SuiResult<Vec<bool>> {
+        Ok(object_keys
+            .iter()
+            .map(|ObjectKey(object_id, version)| self.received.contains(&(*object_id, *version)))
+            .collect())
+    }
+}
+
+#[test]
+fn test_validate_receiving_objects() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let parent = SuiAddress::random_for_testing_only();
+    let object = Object::with_owner_for_testing(parent);
+    let (object_id, version, digest) = object.compute_object_reference();
+    let mut store = MockReceivingStore::default();
+    store.objects.insert(object_id, object.clone());
+    let inputs = InputObjects::new(vec![]);
+    let validate = |store: &MockReceivingStore, object_ref| {
+        validate_receiving_objects(store, &[object_ref], &inputs, &protocol_config, 0)
+    };
+
+    let loaded = validate(&store, (object_id, version, digest)).unwrap();
+    assert_eq!(loaded.iter().count(), 1);
+
+    let stale_version = SequenceNumber::from_u64(version.value() + 1);
+    assert_eq!(
+        validate(&store, (object_id, stale_version, digest)).unwrap_err(),
+        SuiError::from(UserInputError::ObjectVersionUnavailableForConsumption {
+            provided_obj_ref: (object_id, stale_version, digest),
+            current_version: version,
+        })
+    );
+
+    let wrong_digest = ObjectDigest::random();
+    assert_eq!(
+        validate(&store, (object_id, version, wrong_digest)).unwrap_err(),
+        SuiError::from(UserInputError::InvalidObjectDigest {
+            object_id,
+            expected_digest: digest,
+        })
+    );
+
+    // Already received at the requested version, so the stored object is not consulted.
+    store.received.insert((object_id, stale_version));
+    validate(&store, (object_id, stale_version, digest)).unwrap();
+
+    let missing = random_object_ref();
+    assert_eq!(
+        validate(&store, missing).unwrap_err(),
+        SuiError::from(UserInputError::ObjectNotFound {
+            object_id: missing.0,
+            version: Some(missing.1),
+        })
+    );
+}
+
+#[test]
+fn test_check_certificate_inputs_batch() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let genesis = VerifiedExecutableTransaction::new_system(
+        VerifiedTransaction::new_genesis_transaction(vec![]),
+        0,
+    );
+    let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+    let gas = Object::with_owner_for_testing(sender);
+    let transfer = VerifiedExecutableTransaction::new_from_checkpoint(
+        VerifiedTransaction::new_unchecked(to_sender_signed_transaction(
+            transfer_objects_tx(sender, vec![], vec![gas.compute_object_reference()], sender),
+            &keypair,
+        )),
+        0,
+        0,
+    );
+
+    // The transfer's gas coin is not among its inputs.
+    let metrics = CertificateCheckBatchMetrics::new(&prometheus::Registry::new());
+    let results = check_certificate_inputs_batch(
+        &[
+            (&genesis, InputObjects::new(vec![])),
+            (&transfer, InputObjects::new(vec![])),
+        ],
+        &protocol_config,
+        TEST_GAS_PRICE,
+        &metrics,
+    );
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(metrics.certificate_check_batch_size.get_sample_sum(), 2.0);
+    assert_eq!(metrics.certificate_check_batch_failures.get(), 1);
+    assert!(results[0].is_ok());
+    let individual = check_certificate_input(
+        &transfer,
+        InputObjects::new(vec![]),
+        &protocol_config,
+        TEST_GAS_PRICE,
+    );
+    assert!(individual.is_err());
+    assert_eq!(results[1].as_ref().err(), individual.as_ref().err());
+}
+
+#[test]
+fn test_check_transaction_input_with_timings() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let metrics = Arc::new(BytecodeVerifierMetrics::new(&prometheus::Registry::new()));
+    let sender = SuiAddress::random_for_testing_only();
+    let object = Object::with_owner_for_testing(sender);
+    let gas = Object::new_gas_with_balance_and_owner_for_testing(100_000_000, sender);
+    let transaction = transfer_objects_tx(
+        sender,
+        vec![object.compute_object_reference()],
+        vec![gas.compute_object_reference()],
+        sender,
+    );
+    let custom_checks: Vec<Arc<dyn TransactionCheck>> =
+        vec![Arc::new(MaxMoveCallsCheck { max_move_calls: 1 })];
+
+    let (result, timings) = check_transaction_input_with_timings(
+        &protocol_config,
+        TEST_GAS_PRICE,
+        &transaction,
+        InputObjects::new(vec![owned_input(&object), owned_input(&gas)]),
+        ReceivingObjects::from(vec![]),
+        &metrics,
+        &TransactionCheckConfig::default(),
+        &custom_checks,
+    );
+
+    assert!(result.is_ok());
+    let CheckTimings {
+        validity,
+        package_verification,
+        input_objects,
+        gas,
+        objects,
+        receiving_objects,
+        custom_checks,
+    } = timings;
+    for phase in [
+        validity,
+        package_verification,
+        input_objects,
+        gas,
+        objects,
+        receiving_objects,
+        custom_checks,
+    ] {
+        assert!(!phase.is_zero());
+    }
+}
+
+#[test]
+fn test_estimate_min_gas_budget() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let min_budget = protocol_config.base_tx_cost_fixed() * TEST_GAS_PRICE;
+    let sender = SuiAddress::random_for_testing_only();
+    let estimate = |balance, reference_gas_price| {
+        let coin = Object::new_gas_with_balance_and_owner_for_testing(balance, sender);
+        let gas = vec![coin.compute_object_reference()];
+        let transaction = transfer_objects_tx(sender, vec![], gas.clone(), sender);
+        estimate_min_gas_budget(
+            &InputObjects::new(vec![owned_input(&coin)]),
+            &gas,
+            &protocol_config,
+            reference_gas_price,
+            &transaction,
+        )
+    };
+
+    assert_eq!(
+        estimate(min_budget * 10, TEST_GAS_PRICE).unwrap(),
+        min_budget
+    );
+    assert_eq!(estimate(min_budget, TEST_GAS_PRICE).unwrap(), min_budget);
+    assert_eq!(
+        estimate(min_budget - 1, TEST_GAS_PRICE).unwrap_err(),
+        UserInputError::GasBalanceTooLow {
+            gas_balance: (min_budget - 1) as u128,
+            needed_gas_amount: min_budget as u128,
+        }
+        .into()
+    );
+
+    // No budget helps a transaction whose gas price is below the reference gas price.
+    assert_eq!(
+        estimate(min_budget * 10, TEST_GAS_PRICE + 1).unwrap_err(),
+        UserInputError::GasPriceUnderRGP {
+            gas_price: TEST_GAS_PRICE,
+            reference_gas_price: TEST_GAS_PRICE + 1,
+        }
+        .into()
+    );
+}
+
+#[test]
+fn test_inconsistent_shared_object_mutability() {
+    let sender = SuiAddress::random_for_testing_only();
+    let shared = Object::shared_for_testing();
+    let gas = Object::with_owner_for_testing(sender);
+    let shared_input = |mutable| {
+        ObjectReadResult::new(
+            InputObjectKind::SharedMoveObject {
+                id: shared.id(),
+                initial_shared_version: shared.version(),
+                mutable,
+            },
+            shared.clone().into(),
+        )
+    };
+    let transaction =
+        transfer_objects_tx(sender, vec![], vec![gas.compute_object_reference()], sender);
+
+    assert_eq!(
+        check_objects(
+            &transaction,
+            &InputObjects::new(vec![shared_input(true), shared_input(false)]),
+        )
+        .unwrap_err(),
+        UserInputError::InconsistentSharedObjectMutability {
+            object_id: shared.id()
+        }
+    );
+}
+
+#[test]
+fn test_oversized_package_rejected_before_verification() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let metrics = Arc::new(BytecodeVerifierMetrics::new(&prometheus::Registry::new()));
+    let max_package_size = protocol_config.max_move_package_size() as usize;
+    let mut builder = ProgrammableTransactionBuilder::new();
+    // Not valid bytecode, which the verifier would reject if it got to run.
+    builder.publish_immutable(
+        vec![
+            vec![0; max_package_size / 2],
+            vec![0; max_package_size / 2 + 1],
+        ],
+        vec![],
+    );
+    let transaction = TransactionData::new_programmable(
+        SuiAddress::random_for_testing_only(),
+        vec![random_object_ref()],
+        builder.finish(),
+        TEST_GAS_BUDGET,
+        TEST_GAS_PRICE,
+    );
+
+    assert_eq!(
+        check_non_system_packages_to_be_published(
+            &transaction,
+            &protocol_config,
+            &metrics,
+            &TransactionCheckConfig::default(),
+        )
+        .unwrap_err(),
+        UserInputError::SizeLimitExceeded {
+            limit: "maximum move package size".to_string(),
+            value: max_package_size.to_string(),
+        }
+    );
+}
+
+/// A module named `name` at `address`, depending on the module `dep` of package `dependency` if
+/// one is given.
+fn module_with_dependency(
+    address: ObjectID,
+    name: &str,
+    dependency: Option<ObjectID>,
+) -> CompiledModule {
+    let mut module = empty_module();
+    module.address_identifiers[0] = address.into();
+    module.identifiers[0] = Identifier::new(name).unwrap();
+    if let Some(dependency) = dependency {
+        module.address_identifiers.push(dependency.into());
+        module.identifiers.push(Identifier::new("dep").unwrap());
+        module.module_handles.push(ModuleHandle {
+            address: AddressIdentifierIndex(1),
+            name: IdentifierIndex(1),
+        });
+    }
+    module
+}
+
+#[test]
+fn test_publish_dependencies_must_be_inputs() {
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let publish_tx = |dependency: ObjectID| {
+        let mut bytes = vec![];
+        module_with_dependency(ObjectID::ZERO, "m", Some(dependency))
+            .serialize(&mut bytes)
+            .unwrap();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.publish_immutable(vec![bytes], vec![dependency]);
+        TransactionData::new_programmable(
+            SuiAddress::random_for_testing_only(),
+            vec![random_object_ref()],
+            builder.finish(),
+            TEST_GAS_BUDGET,
+            TEST_GAS_PRICE,
+        )
+    };
+
+    let dependency = Object::new_package_for_testing(
+        &[module_with_dependency(ObjectID::random(), "dep", None)],
+        TransactionDigest::genesis(),
+        [],
+    )
+    .unwrap();
+    let package_id = dependency.id();
+    let transaction = publish_tx(package_id);
+
+    assert_eq!(
+        check_publish_dependencies(&transaction, &InputObjects::new(vec![]), &protocol_config)
+            .unwrap_err(),
+        UserInputError::MissingPublishDependency { package_id }
+    );
+    let inputs = InputObjects::new(vec![ObjectReadResult::new(
+        InputObjectKind::MovePackage(package_id),
+        dependency.into(),
+    )]);
+    check_publish_dependencies(&transaction, &inputs, &protocol_config).unwrap();
+
+    // System packages never need to be supplied.
+    check_publish_dependencies(
+        &publish_tx(SUI_FRAMEWORK_PACKAGE_ID),
+        &InputObjects::new(vec![]),
+        &protocol_config,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_check_objects_many_immutable_inputs() {
+    const NUM_IMMUTABLES: usize = 4096;
+    let sender = SuiAddress::random_for_testing_only();
+    let gas = Object::with_owner_for_testing(sender);
+    let immutables: Vec<_> = (0..NUM_IMMUTABLES)
+        .map(|_| Object::immutable_with_id_for_testing(ObjectID::random()))
+        .collect();
+    let transaction =
+        transfer_objects_tx(sender, vec![], vec![gas.compute_object_reference()], sender);
+
+    let mut inputs: Vec<_> = immutables.iter().map(owned_input).collect();
+    inputs.push(owned_input(&gas));
+    check_objects(&transaction, &InputObjects::new(inputs.clone())).unwrap();
+
+    // No owner is looked up for the immutable inputs, only for the gas coin.
+    let gas_ids = HashSet::from([gas.id()]);
+    for immutable in &immutables {
+        assert_eq!(
+            expected_owner_for_input(&transaction, &gas_ids, immutable),
+            None
+        );
+    }
+    assert_eq!(
+        expected_owner_for_input(&transaction, &gas_ids, &gas),
+        Some(sender)
+    );
+
+    // The digest is still checked for immutable objects.
+    let (object_id, version, _) = immutables[NUM_IMMUTABLES / 2].compute_object_reference();
+    inputs[NUM_IMMUTABLES / 2].input_object_kind =
+        InputObjectKind::ImmOrOwnedMoveObject((object_id, version, ObjectDigest::random()));
+    assert!(matches!(
+        check_objects(&transaction, &InputObjects::new(inputs)).unwrap_err(),
+        UserInputError::InvalidObjectDigest { object_id: id, .. } if id == object_id
+    ));
+}
@@ -0,0 +1,158 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use std::collections::HashSet;
+use sui_types::{
+    base_types::{random_object_ref, ObjectID, SequenceNumber, SuiAddress},
+    digests::{ObjectDigest, TransactionDigest},
+    object::Owner,
+};
+
+/// One instance of every error the checks in this crate can emit, except for the variants that
+/// deliberately share a code with one of these (see `test_aliased_codes`).
+fn emitted_errors() -> Vec<UserInputError> {
+    let object_id = ObjectID::random();
+    vec![
+        UserInputError::ObjectNotFound {
+            object_id,
+            version: None,
+        },
+        UserInputError::ObjectVersionUnavailableForConsumption {
+            provided_obj_ref: random_object_ref(),
+            current_version: SequenceNumber::new(),
+        },
+        UserInputError::InvalidObjectDigest {
+            object_id,
+            expected_digest: ObjectDigest::random(),
+        },
+        UserInputError::InvalidSequenceNumber,
+        UserInputError::DuplicateObjectRefInput,
+        UserInputError::MutableObjectUsedMoreThanOnceAt {
+            object_id,
+            first_input: 0,
+            second_input: 1,
+        },
+        UserInputError::ObjectInputArityViolation,
+        UserInputError::MoveObjectAsPackage { object_id },
+        UserInputError::MovePackageAsObject { object_id },
+        UserInputError::InvalidChildObjectArgument {
+            child_id: object_id,
+            parent_id: ObjectID::random(),
+        },
+        UserInputError::IncorrectUserSignature {
+            error: String::new(),
+        },
+        UserInputError::NotSharedObjectError,
+        UserInputError::SharedObjectStartingVersionMismatch,
+        UserInputError::DeletedSharedObjectDigestMismatch {
+            object_id,
+            expected_digest: TransactionDigest::random(),
+            actual_digest: TransactionDigest::random(),
+        },
+        UserInputError::ImmutableParameterExpectedError { object_id },
+        UserInputError::MutableParameterExpected { object_id },
+        UserInputError::InconsistentSharedObjectMutability { object_id },
+        UserInputError::InaccessibleSystemObject { object_id },
+        UserInputError::ClockOrderingViolation,
+        UserInputError::TypeMismatch {
+            object_id,
+            expected: String::new(),
+            actual: String::new(),
+        },
+        UserInputError::UpgradeCapNotFound {
+            package_id: object_id,
+        },
+        UserInputError::MissingPublishDependency {
+            package_id: object_id,
+        },
+        UserInputError::MissingGasPayment,
+        UserInputError::GasCoinsNotFound {
+            missing: vec![object_id],
+        },
+        UserInputError::InvalidGasObject { object_id },
+        UserInputError::GasObjectNotOwnedObject {
+            owner: Owner::AddressOwner(SuiAddress::ZERO),
+        },
+        UserInputError::GasObjectUsedAsSenderInput { object_id },
+        UserInputError::GasBudgetTooLow {
+            gas_budget: 0,
+            min_budget: 1,
+        },
+        UserInputError::GasBalanceTooLow {
+            gas_balance: 0,
+            needed_gas_amount: 1,
+        },
+        UserInputError::GasPriceExceedsRGPMultiple {
+            gas_price: 2,
+            max_allowed: 1,
+        },
+        UserInputError::GasPriceUnderRGP {
+            gas_price: 0,
+            reference_gas_price: 1,
+        },
+        UserInputError::GasPriceTooHigh { max_gas_price: 1 },
+        UserInputError::GasBudgetTooHigh {
+            gas_budget: 2,
+            max_budget: 1,
+        },
+        UserInputError::SizeLimitExceeded {
+            limit: String::new(),
+            value: String::new(),
+        },
+        UserInputError::PackageVerificationTimedout { err: String::new() },
+        UserInputError::TransactionDenied {
+            error: String::new(),
+        },
+        UserInputError::Unsupported(String::new()),
+        UserInputError::MalformedGenesisTransaction {
+            error: String::new(),
+        },
+    ]
+}
+
+#[test]
+fn test_every_emitted_error_has_a_distinct_code() {
+    let errors = emitted_errors();
+    let mut codes = HashSet::new();
+    for error in &errors {
+        let code = code(error);
+        assert_ne!(code, CheckErrorCode::Other, "{error:?} has no code");
+        assert!(codes.insert(code), "{error:?} shares code {code:?}");
+    }
+}
+
+#[test]
+fn test_aliased_codes() {
+    let object_id = ObjectID::random();
+    assert_eq!(
+        code(&UserInputError::MutableObjectUsedMoreThanOnce { object_id }),
+        code(&UserInputError::MutableObjectUsedMoreThanOnceAt {
+            object_id,
+            first_input: 0,
+            second_input: 1,
+        }),
+    );
+    assert_eq!(
+        code(&UserInputError::PackageVerificationTimedout { err: String::new() }),
+        code(&UserInputError::PackageVerificationTimedoutInCommand {
+            command_index: 0,
+            err: String::new(),
+        }),
+    );
+}
+
+#[test]
+fn test_codes_are_stable() {
+    // Clients depend on these values, so changing any of them is a breaking change.
+    assert_eq!(CheckErrorCode::Other.as_u16(), 0);
+    assert_eq!(CheckErrorCode::ObjectNotFound.as_u16(), 1);
+    assert_eq!(CheckErrorCode::UpgradeCapNotFound.as_u16(), 21);
+    assert_eq!(CheckErrorCode::MissingGasPayment.as_u16(), 100);
+    assert_eq!(CheckErrorCode::GasPriceExceedsRGPMultiple.as_u16(), 107);
+    assert_eq!(CheckErrorCode::GasBudgetTooHigh.as_u16(), 110);
+    assert_eq!(CheckErrorCode::SizeLimitExceeded.as_u16(), 200);
+    assert_eq!(CheckErrorCode::PackageVerificationTimedout.as_u16(), 201);
+    assert_eq!(CheckErrorCode::TransactionDenied.as_u16(), 300);
+    assert_eq!(CheckErrorCode::MalformedGenesisTransaction.as_u16(), 302);
+}
@@ -248,6 +248,50 @@ pub enum UserInputError {
 
     #[error("Immutable parameter provided, mutable parameter expected.")]
     MutableParameterExpected { object_id: ObjectID },
+
+    #[error("Gas object {object_id} is owned by the sponsor and cannot also be used as an input by the sender.")]
+    GasObjectUsedAsSenderInput { object_id: ObjectID },
+
+    #[error("Malformed genesis transaction: {error}")]
+    MalformedGenesisTransaction { error: String },
+
+    #[error("Deleted shared object {object_id} was deleted by transaction {actual_digest}, expected {expected_digest}")]
+    DeletedSharedObjectDigestMismatch {
+        object_id: ObjectID,
+        expected_digest: TransactionDigest,
+        actual_digest: TransactionDigest,
+    },
+
+    #[error("Upgrading package {package_id} requires an UpgradeCap for it owned by the sender")]
+    UpgradeCapNotFound { package_id: ObjectID },
+
+    #[error("Object {object_id} has type {actual}, but the transaction expects {expected}")]
+    TypeMismatch {
+        object_id: ObjectID,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Verification of the package in command {command_index} timed out: {err:?}")]
+    PackageVerificationTimedoutInCommand { command_index: usize, err: String },
+    #[error(
+        "Mutable object {object_id} cannot appear more than one in one transaction: used at inputs {first_input} and {second_input}."
+    )]
+    MutableObjectUsedMoreThanOnceAt {
+        object_id: ObjectID,
+        first_input: usize,
+        second_input: usize,
+    },
+    #[error("Gas coins not found: {missing:?}")]
+    GasCoinsNotFound { missing: Vec<ObjectID> },
+    #[error("Gas price {gas_price} cannot exceed {max_allowed} mist, a multiple of the reference gas price")]
+    GasPriceExceedsRGPMultiple { gas_price: u64, max_allowed: u64 },
+    #[error("The Clock must come after every other shared object input")]
+    ClockOrderingViolation,
+    #[error("Shared object {object_id} is used both mutably and immutably")]
+    InconsistentSharedObjectMutability { object_id: ObjectID },
+    #[error("Published package depends on package {package_id}, which is not an input of the transaction")]
+    MissingPublishDependency { package_id: ObjectID },
 }
 
 #[derive(
@@ -59,6 +59,23 @@ pub mod checked {
             reference_gas_price: u64,
             config: &ProtocolConfig,
         ) -> SuiResult<Self> {
+            Self::check_gas_price(gas_price, reference_gas_price, config)?;
+
+            Ok(Self::V2(SuiGasStatusV2::new_with_budget(
+                gas_budget,
+                gas_price,
+                reference_gas_price,
+                config,
+            )))
+        }
+
+        /// Checks that `gas_price` is within the bounds allowed by `config` and the current
+        /// reference gas price.
+        pub fn check_gas_price(
+            gas_price: u64,
+            reference_gas_price: u64,
+            config: &ProtocolConfig,
+        ) -> UserInputResult {
             // Common checks. We may pull them into version specific status as needed, but they
             // are unlikely to change.
 
@@ -67,30 +84,31 @@ pub mod checked {
                 return Err(UserInputError::GasPriceUnderRGP {
                     gas_price,
                     reference_gas_price,
-                }
-                .into());
+                });
             }
             if gas_price_too_high(config.gas_model_version()) && gas_price >= config.max_gas_price()
             {
                 return Err(UserInputError::GasPriceTooHigh {
                     max_gas_price: config.max_gas_price(),
+                });
+            }
+            if let Some(multiplier) = config.max_gas_price_rgp_multiplier_as_option() {
+                let max_allowed = reference_gas_price.saturating_mul(multiplier);
+                if gas_price > max_allowed {
+                    return Err(UserInputError::GasPriceExceedsRGPMultiple {
+                        gas_price,
+                        max_allowed,
+                    });
                 }
-                .into());
             }
-
-            Ok(Self::V2(SuiGasStatusV2::new_with_budget(
-                gas_budget,
-                gas_price,
-                reference_gas_price,
-                config,
-            )))
+            Ok(())
         }
 
         pub fn new_unmetered() -> Self {
             Self::V2(SuiGasStatusV2::new_unmetered())
         }
 
-        // This is the only public API on SuiGasStatus, all other gas related operations should
+        // These are the only public APIs on SuiGasStatus, all other gas related operations should
         // go through `GasCharger`
         pub fn check_gas_balance(
             &self,
@@ -101,6 +119,19 @@ pub mod checked {
                 Self::V2(status) => status.check_gas_balance(gas_objs, gas_budget),
             }
         }
+
+        pub fn check_gas_budget(&self, gas_budget: u64) -> UserInputResult {
+            match self {
+                Self::V2(status) => status.check_gas_budget(gas_budget),
+            }
+        }
+
+        /// The smallest budget that passes `check_gas_budget`, i.e. the minimum transaction cost.
+        pub fn min_gas_budget(&self) -> u64 {
+            match self {
+                Self::V2(status) => status.min_gas_budget(),
+            }
+        }
     }
 
     /// Summary of the charges in a transaction.
@@ -272,10 +272,30 @@ mod checked {
             )
         }
 
+        pub(crate) fn min_gas_budget(&self) -> u64 {
+            self.cost_table.min_transaction_cost
+        }
+
         // Check whether gas arguments are legit:
         // 1. Gas object has an address owner.
         // 2. Gas budget is between min and max budget allowed
         // 3. Gas balance (all gas coins together) is bigger or equal to budget
+        pub(crate) fn check_gas_budget(&self, gas_budget: u64) -> UserInputResult {
+            if gas_budget > self.cost_table.max_gas_budget {
+                return Err(UserInputError::GasBudgetTooHigh {
+                    gas_budget,
+                    max_budget: self.cost_table.max_gas_budget,
+                });
+            }
+            if gas_budget < self.cost_table.min_transaction_cost {
+                return Err(UserInputError::GasBudgetTooLow {
+                    gas_budget,
+                    min_budget: self.cost_table.min_transaction_cost,
+                });
+            }
+            Ok(())
+        }
+
         pub(crate) fn check_gas_balance(
             &self,
             gas_objs: &[&ObjectReadResult],
@@ -297,18 +317,7 @@ mod checked {
             }
 
             // 2. Gas budget is between min and max budget allowed
-            if gas_budget > self.cost_table.max_gas_budget {
-                return Err(UserInputError::GasBudgetTooHigh {
-                    gas_budget,
-                    max_budget: self.cost_table.max_gas_budget,
-                });
-            }
-            if gas_budget < self.cost_table.min_transaction_cost {
-                return Err(UserInputError::GasBudgetTooLow {
-                    gas_budget,
-                    min_budget: self.cost_table.min_transaction_cost,
-                });
-            }
+            self.check_gas_budget(gas_budget)?;
 
             // 3. Gas balance (all gas coins together) is bigger or equal to budget
             let mut gas_balance = 0u128;
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_vec_with_registry, Histogram,
-    IntCounterVec,
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, Histogram, IntCounter, IntCounterVec,
 };
 
 pub struct LimitsMetrics {
@@ -84,6 +84,15 @@ pub struct BytecodeVerifierMetrics {
     pub verifier_runtime_per_module_timeout_latency: Histogram,
     /// Bytecode verifier runtime latency for each programmable transaction block which timed out
     pub verifier_runtime_per_ptb_timeout_latency: Histogram,
+    /// Bytecode verifier runtime latency for each package successfully verified, when packages
+    /// are metered independently
+    pub verifier_runtime_per_package_success_latency: Histogram,
+    /// Bytecode verifier runtime latency for each package which timed out, when packages are
+    /// metered independently
+    pub verifier_runtime_per_package_timeout_latency: Histogram,
+    /// Fraction of the verifier meter budget used by the most expensive module or function of
+    /// packages that verified successfully
+    pub verifier_meter_usage_fraction: Histogram,
 }
 
 impl BytecodeVerifierMetrics {
@@ -98,6 +107,8 @@ impl BytecodeVerifierMetrics {
         0.025_000, 0.050_000, 0.100_000, 0.250_000, 0.500_000, 1.000_000, /* sub 1 s */
         10.000_000, 20.000_000, 50.000_000, 100.0, /* We should almost never get here */
     ];
+    const USAGE_FRACTION_BUCKETS: &[f64] =
+        &[0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 0.95, 1.0];
     pub fn new(registry: &prometheus::Registry) -> Self {
         Self {
             verifier_timeout_metrics: register_int_counter_vec_with_registry!(
@@ -133,6 +144,68 @@ impl BytecodeVerifierMetrics {
                 Self::LATENCY_SEC_BUCKETS.to_vec(),
                 registry
             ).unwrap(),
+            verifier_runtime_per_package_success_latency: register_histogram_with_registry!(
+                "verifier_runtime_per_package_success_latency",
+                "Time spent running bytecode verifier to completion for a single package at `transaction_input_checker::check_non_system_packages_to_be_published`",
+                Self::LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            ).unwrap(),
+            verifier_runtime_per_package_timeout_latency: register_histogram_with_registry!(
+                "verifier_runtime_per_package_timeout_latency",
+                "Time spent running bytecode verifier to timeout for a single package at `transaction_input_checker::check_non_system_packages_to_be_published`",
+                Self::LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            ).unwrap(),
+            verifier_meter_usage_fraction: register_histogram_with_registry!(
+                "verifier_meter_usage_fraction",
+                "Fraction of the bytecode verifier meter budget used by packages that verified successfully at `transaction_input_checker::check_non_system_packages_to_be_published`",
+                Self::USAGE_FRACTION_BUCKETS.to_vec(),
+                registry
+            ).unwrap(),
+        }
+    }
+}
+
+pub struct CertificateCheckBatchMetrics {
+    /// Number of certificates in each batch whose inputs are checked together
+    pub certificate_check_batch_size: Histogram,
+    /// Number of certificates in checked batches whose inputs failed the checks
+    pub certificate_check_batch_failures: IntCounter,
+    /// Time spent checking the inputs of a whole batch of certificates
+    pub certificate_check_batch_latency: Histogram,
+}
+
+impl CertificateCheckBatchMetrics {
+    const BATCH_SIZE_BUCKETS: &'static [f64] = &[
+        1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1_000.0, 2_000.0, 5_000.0,
+    ];
+    const LATENCY_SEC_BUCKETS: &'static [f64] = &[
+        0.000_010, 0.000_050, 0.000_100, 0.000_500, 0.001_000, 0.005_000, 0.010_000, 0.050_000,
+        0.100_000, 0.500_000, 1.000_000, 5.000_000,
+    ];
+
+    pub fn new(registry: &prometheus::Registry) -> Self {
+        Self {
+            certificate_check_batch_size: register_histogram_with_registry!(
+                "certificate_check_batch_size",
+                "Number of certificates in each batch checked by `check_certificate_inputs_batch`",
+                Self::BATCH_SIZE_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
+            certificate_check_batch_failures: register_int_counter_with_registry!(
+                "certificate_check_batch_failures",
+                "Number of certificates whose inputs failed the checks in `check_certificate_inputs_batch`",
+                registry
+            )
+            .unwrap(),
+            certificate_check_batch_latency: register_histogram_with_registry!(
+                "certificate_check_batch_latency",
+                "Time spent checking a whole batch of certificates in `check_certificate_inputs_batch`",
+                Self::LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
         }
     }
 }
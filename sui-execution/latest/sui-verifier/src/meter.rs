@@ -5,27 +5,55 @@ use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_bytecode_verifier::meter::{Meter, Scope};
 use move_core_types::vm_status::StatusCode;
 use move_vm_config::verifier::VerifierConfig;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 struct SuiVerifierMeterBounds {
     name: String,
     ticks: u128,
+    /// Highest value `ticks` reached in any scope entered so far. Unlike `ticks`, this is not
+    /// reset on `enter_scope`.
+    peak_ticks: u128,
     max_ticks: Option<u128>,
 }
 
 impl SuiVerifierMeterBounds {
     fn add(&mut self, ticks: u128) -> PartialVMResult<()> {
+        let new_ticks = self.check(self.ticks, ticks)?;
+        self.ticks = new_ticks;
+        self.peak_ticks = self.peak_ticks.max(new_ticks);
+        Ok(())
+    }
+
+    /// Like `add`, but checks the limit against `shared`, the ticks of every meter splitting this
+    /// scope's budget, instead of against this meter's own ticks.
+    fn add_shared(&mut self, shared: &AtomicU64, ticks: u128) -> PartialVMResult<()> {
+        let shared_ticks = u64::try_from(ticks).unwrap_or(u64::MAX);
+        // The update never fails, both variants hold the previous total.
+        let previous = shared
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |total| {
+                Some(total.saturating_add(shared_ticks))
+            })
+            .unwrap_or_else(|previous| previous);
+        let new_total = self.check(previous as u128, ticks)?;
+        self.ticks = self.ticks.saturating_add(ticks);
+        self.peak_ticks = self.peak_ticks.max(new_total);
+        Ok(())
+    }
+
+    /// The ticks after adding `ticks` to `current`, failing if that reaches the limit.
+    fn check(&self, current: u128, ticks: u128) -> PartialVMResult<u128> {
         let max_ticks = self.max_ticks.unwrap_or(u128::MAX);
 
-        let new_ticks = self.ticks.saturating_add(ticks);
+        let new_ticks = current.saturating_add(ticks);
         if new_ticks >= max_ticks {
             return Err(PartialVMError::new(StatusCode::PROGRAM_TOO_COMPLEX)
                     .with_message(format!(
                         "program too complex. Ticks exceeded `{}` will exceed limits: `{} current + {} new > {} max`)",
-                        self.name, self.ticks, ticks, max_ticks
+                        self.name, current, ticks, max_ticks
                     )));
         }
-        self.ticks = new_ticks;
-        Ok(())
+        Ok(new_ticks)
     }
 }
 
@@ -34,6 +62,8 @@ pub struct SuiVerifierMeter {
     package_bounds: SuiVerifierMeterBounds,
     module_bounds: SuiVerifierMeterBounds,
     function_bounds: SuiVerifierMeterBounds,
+    /// Module ticks of every meter sharing one module budget, see `new_with_shared_module_ticks`.
+    shared_module_ticks: Option<Arc<AtomicU64>>,
 }
 
 impl SuiVerifierMeter {
@@ -42,6 +72,7 @@ impl SuiVerifierMeter {
             transaction_bounds: SuiVerifierMeterBounds {
                 name: "<unknown>".to_string(),
                 ticks: 0,
+                peak_ticks: 0,
                 max_ticks: None,
             },
 
@@ -49,18 +80,36 @@ impl SuiVerifierMeter {
             package_bounds: SuiVerifierMeterBounds {
                 name: "<unknown>".to_string(),
                 ticks: 0,
+                peak_ticks: 0,
                 max_ticks: None,
             },
             module_bounds: SuiVerifierMeterBounds {
                 name: "<unknown>".to_string(),
                 ticks: 0,
+                peak_ticks: 0,
                 max_ticks: config.max_per_mod_meter_units,
             },
             function_bounds: SuiVerifierMeterBounds {
                 name: "<unknown>".to_string(),
                 ticks: 0,
+                peak_ticks: 0,
                 max_ticks: config.max_per_fun_meter_units,
             },
+            shared_module_ticks: None,
+        }
+    }
+
+    /// A meter whose module ticks count against `shared_module_ticks` together with those of
+    /// every other meter created with it. The module scope is never re-entered while verifying a
+    /// transaction, so its limit bounds all the modules that one meter verifies: sharing it lets
+    /// several meters verify those modules concurrently within the same budget.
+    pub fn new_with_shared_module_ticks(
+        config: &VerifierConfig,
+        shared_module_ticks: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            shared_module_ticks: Some(shared_module_ticks),
+            ..Self::new(config)
         }
     }
 
@@ -89,6 +138,23 @@ impl SuiVerifierMeter {
     pub fn get_limit(&self, scope: Scope) -> Option<u128> {
         self.get_bounds(scope).max_ticks
     }
+
+    /// Highest usage seen in `scope` across every time it was entered by this meter.
+    pub fn get_peak_usage(&self, scope: Scope) -> u128 {
+        self.get_bounds(scope).peak_ticks
+    }
+
+    /// Fraction of the tightest bounded limit (module or function) that the peak usage reached,
+    /// in `[0, 1]`. Returns `None` if neither scope is bounded.
+    pub fn peak_usage_fraction(&self) -> Option<f64> {
+        [Scope::Module, Scope::Function]
+            .into_iter()
+            .filter_map(|scope| {
+                let limit = self.get_limit(scope)?;
+                Some(self.get_peak_usage(scope) as f64 / limit.max(1) as f64)
+            })
+            .reduce(f64::max)
+    }
 }
 
 impl Meter for SuiVerifierMeter {
@@ -104,6 +170,9 @@ impl Meter for SuiVerifierMeter {
     }
 
     fn add(&mut self, scope: Scope, ticks: u128) -> PartialVMResult<()> {
+        if let (Scope::Module, Some(shared)) = (scope, &self.shared_module_ticks) {
+            return self.module_bounds.add_shared(shared, ticks);
+        }
         self.get_bounds_mut(scope).add(ticks)
     }
 }
@@ -1,7 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::HashSet,
+    sync::{atomic::AtomicU64, Arc},
+};
 
 use move_binary_format::CompiledModule;
 use move_vm_config::verifier::VerifierConfig;
@@ -210,4 +213,14 @@ impl<'m> verifier::Verifier for Verifier<'m> {
             mod_meter_units_result,
         ))
     }
+
+    fn meter_usage_fraction(&self) -> Option<f64> {
+        self.meter.peak_usage_fraction()
+    }
+
+    fn share_module_budget(&mut self, shared_module_ticks: Arc<AtomicU64>) -> bool {
+        self.meter =
+            SuiVerifierMeter::new_with_shared_module_ticks(&self.config, shared_module_ticks);
+        true
+    }
 }
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_binary_format::CompiledModule;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::error::SuiResult;
 
@@ -46,6 +48,20 @@ pub trait Verifier {
         protocol_config: &ProtocolConfig,
         config_overrides: &VerifierOverrides,
     ) -> SuiResult<VerifierMeteredValues>;
+
+    /// Fraction of the meter budget consumed by the most expensive module or function verified
+    /// so far, or `None` if this verifier does not track it or the meter is unbounded.
+    fn meter_usage_fraction(&self) -> Option<f64> {
+        None
+    }
+
+    /// Makes this verifier's module meter count against `shared_module_ticks` together with every
+    /// other verifier sharing it, so that they can verify the packages of one transaction
+    /// concurrently within the budget of a single verifier. Must be called before verifying
+    /// anything. Returns `false`, leaving the verifier unchanged, if it does not support sharing.
+    fn share_module_budget(&mut self, _shared_module_ticks: Arc<AtomicU64>) -> bool {
+        false
+    }
 }
 
 /// Controls verifier config values to override.